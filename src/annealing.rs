@@ -0,0 +1,350 @@
+use std::{collections::HashSet, time::Instant};
+
+use crate::{NoPossibleDispersion, Point};
+
+/// Small, fast xorshift64* generator. Not cryptographically secure, but
+/// reproducible given a seed, which is what the local search needs.
+struct Xorshift64 {
+    state: u64,
+}
+
+impl Xorshift64 {
+    fn new(seed: u64) -> Self {
+        // xorshift is undefined on an all-zero state, so nudge it away from one.
+        Self {
+            state: if seed == 0 { 0x9e3779b97f4a7c15 } else { seed },
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+
+    fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    fn next_unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+}
+
+struct TimeKeeper {
+    start: Instant,
+    limit_ms: u64,
+}
+
+impl TimeKeeper {
+    fn new(limit_ms: u64) -> Self {
+        Self {
+            start: Instant::now(),
+            limit_ms,
+        }
+    }
+
+    fn elapsed_fraction(&self) -> f64 {
+        if self.limit_ms == 0 {
+            return 1.0;
+        }
+        (self.start.elapsed().as_millis() as f64 / self.limit_ms as f64).min(1.0)
+    }
+
+    fn is_expired(&self) -> bool {
+        self.start.elapsed().as_millis() as u64 >= self.limit_ms
+    }
+}
+
+/// Gonzalez farthest-first traversal, used here purely to seed the annealer
+/// with a spread-out starting selection instead of a random one.
+fn farthest_first_init(points: &[Point], p: usize, rng: &mut Xorshift64) -> Vec<usize> {
+    let n = points.len();
+    let mut selected = Vec::with_capacity(p);
+    let mut dist_to_set = vec![f32::INFINITY; n];
+
+    let seed = rng.next_below(n);
+    selected.push(seed);
+    dist_to_set[seed] = -1.;
+
+    while selected.len() < p {
+        for (index, slot) in dist_to_set.iter_mut().enumerate() {
+            if *slot < 0. {
+                continue;
+            }
+            *slot = slot.min(points[index].get_distance(&points[*selected.last().unwrap()]));
+        }
+
+        let next = dist_to_set
+            .iter()
+            .enumerate()
+            .filter(|(_, dist)| **dist >= 0.)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+            .expect("p <= n, so an unselected point must remain");
+
+        selected.push(next);
+        dist_to_set[next] = -1.;
+    }
+
+    selected
+}
+
+/// Per-selected-point nearest-neighbour cache: `dist[i]` is the distance from
+/// `selected[i]` to the closest other selected point, and `neighbour[i]` is
+/// the position (within `selected`) that achieves it. The minimum of `dist`
+/// is the dispersion objective.
+struct NeighbourCache {
+    dist: Vec<f32>,
+    neighbour: Vec<usize>,
+}
+
+impl NeighbourCache {
+    fn build(points: &[Point], selected: &[usize]) -> Self {
+        let p = selected.len();
+        let mut dist = vec![f32::INFINITY; p];
+        let mut neighbour = vec![0; p];
+
+        for i in 0..p {
+            let (best_dist, best_index) = closest_other(points, selected, i);
+            dist[i] = best_dist;
+            neighbour[i] = best_index;
+        }
+
+        Self { dist, neighbour }
+    }
+
+    fn objective(&self) -> f32 {
+        self.dist.iter().copied().fold(f32::INFINITY, f32::min)
+    }
+}
+
+fn closest_other(points: &[Point], selected: &[usize], position: usize) -> (f32, usize) {
+    let mut best_dist = f32::INFINITY;
+    let mut best_index = position;
+
+    for (other, &other_point) in selected.iter().enumerate() {
+        if other == position {
+            continue;
+        }
+        let dist = points[selected[position]].get_distance(&points[other_point]);
+        if dist < best_dist {
+            best_dist = dist;
+            best_index = other;
+        }
+    }
+
+    (best_dist, best_index)
+}
+
+/// Applies a swap of `selected[position]` for `new_point` to `cache`,
+/// keeping it an exact nearest-neighbour cache rather than an approximation.
+fn apply_swap(
+    points: &[Point],
+    selected: &mut [usize],
+    cache: &mut NeighbourCache,
+    position: usize,
+    new_point: usize,
+) {
+    selected[position] = new_point;
+
+    let p = selected.len();
+    for i in 0..p {
+        if i == position {
+            continue;
+        }
+        let dist = points[selected[i]].get_distance(&points[new_point]);
+        if dist < cache.dist[i] {
+            cache.dist[i] = dist;
+            cache.neighbour[i] = position;
+        } else if cache.neighbour[i] == position {
+            // The neighbour this entry relied on just changed identity, so the
+            // cached distance may now be stale low; only a full rescan can
+            // tell what the new closest point is.
+            let (best_dist, best_index) = closest_other(points, selected, i);
+            cache.dist[i] = best_dist;
+            cache.neighbour[i] = best_index;
+        }
+    }
+
+    let (best_dist, best_index) = closest_other(points, selected, position);
+    cache.dist[position] = best_dist;
+    cache.neighbour[position] = best_index;
+}
+
+/// Time-bounded simulated annealing for p-dispersion on large point sets,
+/// where the exact branch-and-bound in [`crate::solve_p_dispersion`] is too
+/// slow. Returns the best selection found within `time_limit_ms`, not a
+/// certified optimum.
+pub fn solve_p_dispersion_annealing(
+    points: &[Point],
+    p: usize,
+    time_limit_ms: u64,
+    seed: u64,
+) -> Result<Box<[usize]>, NoPossibleDispersion> {
+    let n = points.len();
+
+    if p > n {
+        return Err(NoPossibleDispersion);
+    }
+
+    if p < 2 {
+        return Ok((0..p).collect());
+    }
+
+    let mut rng = Xorshift64::new(seed);
+
+    if p == n {
+        return Ok((0..n).collect());
+    }
+
+    let mut selected = farthest_first_init(points, p, &mut rng);
+    let mut in_selection: HashSet<usize> = selected.iter().copied().collect();
+    let mut cache = NeighbourCache::build(points, &selected);
+
+    let mut best_selection = selected.clone();
+    let mut best_objective = cache.objective();
+
+    let start_temperature = best_objective.max(1.0) as f64;
+    let time_keeper = TimeKeeper::new(time_limit_ms);
+
+    while !time_keeper.is_expired() {
+        let position = rng.next_below(p);
+        let old_point = selected[position];
+
+        let mut new_point = rng.next_below(n);
+        while in_selection.contains(&new_point) {
+            new_point = rng.next_below(n);
+        }
+
+        let mut trial_selected = selected.clone();
+        let mut trial_cache = NeighbourCache {
+            dist: cache.dist.clone(),
+            neighbour: cache.neighbour.clone(),
+        };
+        apply_swap(
+            points,
+            &mut trial_selected,
+            &mut trial_cache,
+            position,
+            new_point,
+        );
+
+        let current_objective = cache.objective();
+        let trial_objective = trial_cache.objective();
+        let delta = (trial_objective - current_objective) as f64;
+
+        let temperature = start_temperature * (1.0 - time_keeper.elapsed_fraction());
+        let accept = delta >= 0.0 || rng.next_unit() < (delta / temperature).exp();
+
+        if accept {
+            selected = trial_selected;
+            cache = trial_cache;
+            in_selection.remove(&old_point);
+            in_selection.insert(new_point);
+
+            if cache.objective() > best_objective {
+                best_objective = cache.objective();
+                best_selection = selected.clone();
+            }
+        }
+    }
+
+    Ok(best_selection.into_boxed_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn min_pairwise(points: &[Point], indices: &[usize]) -> f32 {
+        let mut min_distance = f32::INFINITY;
+        for (position, &a) in indices.iter().enumerate() {
+            for &b in &indices[position + 1..] {
+                min_distance = min_distance.min(points[a].get_distance(&points[b]));
+            }
+        }
+        min_distance
+    }
+
+    /// Exhaustively tries every `select_size`-sized subset of `points` and
+    /// returns the best achievable minimum pairwise distance, the optimum
+    /// the time-bounded annealer can only approach, never beat.
+    fn brute_force_dispersion(points: &[Point], select_size: usize) -> f32 {
+        let n = points.len();
+        let mut best = f32::NEG_INFINITY;
+
+        for mask in 0u32..(1 << n) {
+            if mask.count_ones() as usize != select_size {
+                continue;
+            }
+            let indices: Vec<usize> = (0..n).filter(|i| mask & (1 << i) != 0).collect();
+            best = best.max(min_pairwise(points, &indices));
+        }
+
+        best
+    }
+
+    #[test]
+    fn annealing_gets_close_to_the_brute_force_optimum() {
+        let points = [
+            Point::new(0., 0.),
+            Point::new(1., 0.),
+            Point::new(0., 5.),
+            Point::new(5., 5.),
+            Point::new(5., 0.),
+            Point::new(2., 2.),
+        ];
+
+        for p in 2..points.len() {
+            let expected = brute_force_dispersion(&points, p);
+
+            for seed in 1..=3 {
+                let indices = solve_p_dispersion_annealing(&points, p, 50, seed)
+                    .expect("p <= points.len() is always feasible");
+                let achieved = min_pairwise(&points, &indices);
+
+                assert_eq!(indices.len(), p);
+                assert!(
+                    achieved <= expected + 1e-3,
+                    "p {p}, seed {seed}: achieved {achieved} beat the brute-force optimum {expected}"
+                );
+                assert!(
+                    achieved >= expected * 0.75,
+                    "p {p}, seed {seed}: achieved {achieved} too far from optimum {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn fewer_than_two_placements_skip_the_annealer() {
+        let points = [Point::new(0., 0.), Point::new(1., 1.)];
+
+        assert_eq!(
+            &*solve_p_dispersion_annealing(&points, 0, 10, 1).unwrap(),
+            &[] as &[usize]
+        );
+        assert_eq!(
+            &*solve_p_dispersion_annealing(&points, 1, 10, 1).unwrap(),
+            &[0]
+        );
+    }
+
+    #[test]
+    fn selecting_every_point_skips_the_annealer() {
+        let points = [Point::new(0., 0.), Point::new(1., 1.), Point::new(2., 2.)];
+        let indices = solve_p_dispersion_annealing(&points, 3, 10, 1).unwrap();
+
+        assert_eq!(&*indices, &[0, 1, 2]);
+    }
+
+    #[test]
+    fn more_placements_than_points_is_infeasible() {
+        let points = [Point::new(0., 0.)];
+        assert!(solve_p_dispersion_annealing(&points, 2, 10, 1).is_err());
+    }
+}
@@ -0,0 +1,331 @@
+use std::{
+    iter::zip,
+    ops::{BitAnd, BitOr, Not},
+};
+
+/// A machine word usable as bitset storage, implemented for `u32` and `u64`
+/// so a [`BitSet`]/[`BitMatrix`] can pick whichever lane width best suits the
+/// target: `u64` halves the word-level work in hot paths like `next` and
+/// `subtract` on 64-bit-capable targets such as WASM.
+pub(crate) trait Word:
+    Copy + PartialEq + BitAnd<Output = Self> + BitOr<Output = Self> + Not<Output = Self>
+{
+    const ZERO: Self;
+    const BITS: u32;
+
+    fn count_ones(self) -> u32;
+    fn trailing_zeros(self) -> u32;
+    fn bit(index: u32) -> Self;
+    fn low_mask(bits: u32) -> Self;
+}
+
+impl Word for u32 {
+    const ZERO: Self = 0;
+    const BITS: u32 = u32::BITS;
+
+    fn count_ones(self) -> u32 {
+        u32::count_ones(self)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        u32::trailing_zeros(self)
+    }
+
+    fn bit(index: u32) -> Self {
+        1 << index
+    }
+
+    fn low_mask(bits: u32) -> Self {
+        (1_u32 << bits) - 1
+    }
+}
+
+impl Word for u64 {
+    const ZERO: Self = 0;
+    const BITS: u32 = u64::BITS;
+
+    fn count_ones(self) -> u32 {
+        u64::count_ones(self)
+    }
+
+    fn trailing_zeros(self) -> u32 {
+        u64::trailing_zeros(self)
+    }
+
+    fn bit(index: u32) -> Self {
+        1 << index
+    }
+
+    fn low_mask(bits: u32) -> Self {
+        (1_u64 << bits) - 1
+    }
+}
+
+fn word_count<W: Word>(len: usize) -> usize {
+    len.div_ceil(W::BITS as usize)
+}
+
+/// A bitset over `len` indices, backed by a word array of `W` sized to
+/// `ceil(len / W::BITS)`, with the population count cached in `true_count`
+/// so [`BitSet::len`] stays O(1) on the hot path of the branch-and-bound
+/// search.
+#[derive(Clone)]
+pub(crate) struct BitSet<W: Word> {
+    data: Box<[W]>,
+    true_count: usize,
+}
+
+impl<W: Word> BitSet<W> {
+    pub(crate) fn new(len: usize, fill: bool) -> Self {
+        let words = word_count::<W>(len);
+
+        if fill {
+            let mut data = vec![!W::ZERO; words];
+            let remainder = len % W::BITS as usize;
+            if remainder != 0 {
+                *data.last_mut().unwrap() = W::low_mask(remainder as u32);
+            }
+
+            Self {
+                data: data.into_boxed_slice(),
+                true_count: len,
+            }
+        } else {
+            Self {
+                data: vec![W::ZERO; words].into_boxed_slice(),
+                true_count: 0,
+            }
+        }
+    }
+
+    pub(crate) fn reset(&mut self, len: usize, fill: bool) {
+        if fill {
+            for word in self.data.iter_mut() {
+                *word = !W::ZERO;
+            }
+
+            let remainder = len % W::BITS as usize;
+            if remainder != 0 {
+                *self.data.last_mut().unwrap() = W::low_mask(remainder as u32);
+            }
+
+            self.true_count = len;
+        } else {
+            for word in self.data.iter_mut() {
+                *word = W::ZERO;
+            }
+            self.true_count = 0;
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.count_ones()
+    }
+
+    pub(crate) fn copy(&mut self, src: &BitSet<W>) {
+        self.data.copy_from_slice(&src.data);
+        self.true_count = src.true_count;
+    }
+
+    pub(crate) fn insert(&mut self, index: usize) {
+        let word = index / W::BITS as usize;
+        let bit = W::bit((index % W::BITS as usize) as u32);
+
+        self.true_count += (self.data[word] & bit != bit) as usize;
+        self.data[word] = self.data[word] | bit;
+    }
+
+    pub(crate) fn remove(&mut self, index: usize) {
+        let word = index / W::BITS as usize;
+        let bit = W::bit((index % W::BITS as usize) as u32);
+
+        self.true_count -= (self.data[word] & bit == bit) as usize;
+        self.data[word] = self.data[word] & !bit;
+    }
+
+    /// Copies `lhs` and inserts `index` into the copy in one pass, so a
+    /// caller building a child state doesn't need a separate `copy` +
+    /// `insert` step.
+    pub(crate) fn insert_and_copy(&mut self, lhs: &BitSet<W>, index: usize) {
+        self.copy(lhs);
+        self.insert(index);
+    }
+
+    /// The lowest set index, if any. The search code's "pick next point"
+    /// idiom relies on this to pull candidates out in index order.
+    pub(crate) fn next(&self) -> Option<usize> {
+        self.data
+            .iter()
+            .position(|&word| word != W::ZERO)
+            .map(|index| W::BITS as usize * index + self.data[index].trailing_zeros() as usize)
+    }
+
+    /// All set indices, in ascending order.
+    pub(crate) fn iter_ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.data.iter().enumerate().flat_map(|(index, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == W::ZERO {
+                    return None;
+                }
+                let bit = word.trailing_zeros();
+                word = word & !W::bit(bit);
+                Some(W::BITS as usize * index + bit as usize)
+            })
+        })
+    }
+
+    pub(crate) fn count_ones(&self) -> usize {
+        self.true_count
+    }
+
+    /// `self |= rhs`, recomputing the cached population count. No current
+    /// caller needs this yet, but it completes the set-algebra trio
+    /// alongside `intersection`/`subtract` for whatever bound or heuristic
+    /// reaches for it next.
+    #[allow(dead_code)]
+    pub(crate) fn union(&mut self, rhs: &[W]) {
+        self.true_count = 0;
+        for (lhs, &rhs) in zip(self.data.iter_mut(), rhs) {
+            *lhs = *lhs | rhs;
+            self.true_count += lhs.count_ones() as usize;
+        }
+    }
+
+    /// `self &= rhs`, recomputing the cached population count.
+    pub(crate) fn intersection(&mut self, rhs: &[W]) {
+        self.true_count = 0;
+        for (lhs, &rhs) in zip(self.data.iter_mut(), rhs) {
+            *lhs = *lhs & rhs;
+            self.true_count += lhs.count_ones() as usize;
+        }
+    }
+
+    /// `self &= !rhs`, recomputing the cached population count.
+    pub(crate) fn subtract(&mut self, rhs: &[W]) {
+        self.true_count = 0;
+        for (lhs, &rhs) in zip(self.data.iter_mut(), rhs) {
+            *lhs = *lhs & !rhs;
+            self.true_count += lhs.count_ones() as usize;
+        }
+    }
+
+    /// Copies `lhs`, then subtracts `rhs` from the copy in one pass.
+    pub(crate) fn subtract_and_copy(&mut self, lhs: &BitSet<W>, rhs: &[W]) {
+        self.copy(lhs);
+        self.subtract(rhs);
+    }
+}
+
+impl<W: Word> From<BitSet<W>> for Box<[usize]> {
+    fn from(val: BitSet<W>) -> Self {
+        val.iter_ones().collect()
+    }
+}
+
+/// A dense row-major matrix of bits, one row per vertex: every row is a
+/// contiguous `&[W]` slice, so it can be fed straight into
+/// [`BitSet::union`]/[`BitSet::intersection`]/[`BitSet::subtract`] without an
+/// intermediate `BitSet` per row the way a `Vec<BitSet<W>>` would need.
+pub(crate) struct BitMatrix<W: Word> {
+    data: Box<[W]>,
+    words_per_row: usize,
+}
+
+impl<W: Word> BitMatrix<W> {
+    pub(crate) fn new(row_count: usize, col_count: usize) -> Self {
+        let words_per_row = word_count::<W>(col_count);
+
+        Self {
+            data: vec![W::ZERO; words_per_row * row_count].into_boxed_slice(),
+            words_per_row,
+        }
+    }
+
+    pub(crate) fn row(&self, index: usize) -> &[W] {
+        let start = index * self.words_per_row;
+        &self.data[start..start + self.words_per_row]
+    }
+
+    pub(crate) fn insert(&mut self, row: usize, col: usize) {
+        let word = col / W::BITS as usize;
+        let bit = W::bit((col % W::BITS as usize) as u32);
+        let cell = &mut self.data[row * self.words_per_row + word];
+
+        *cell = *cell | bit;
+    }
+
+    pub(crate) fn remove(&mut self, row: usize, col: usize) {
+        let word = col / W::BITS as usize;
+        let bit = W::bit((col % W::BITS as usize) as u32);
+        let cell = &mut self.data[row * self.words_per_row + word];
+
+        *cell = *cell & !bit;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_remove_and_next_stay_in_index_order() {
+        let mut set = BitSet::<u64>::new(10, false);
+        set.insert(3);
+        set.insert(9);
+        set.insert(0);
+
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.next(), Some(0));
+        assert_eq!(set.iter_ones().collect::<Vec<_>>(), vec![0, 3, 9]);
+
+        set.remove(3);
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.iter_ones().collect::<Vec<_>>(), vec![0, 9]);
+    }
+
+    #[test]
+    fn next_and_iter_ones_cross_word_boundaries() {
+        // u32 lanes make it cheap to span multiple words with a small len.
+        let mut set = BitSet::<u32>::new(70, false);
+        set.insert(31);
+        set.insert(32);
+        set.insert(33);
+        set.insert(69);
+
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.iter_ones().collect::<Vec<_>>(), vec![31, 32, 33, 69]);
+
+        set.remove(31);
+        assert_eq!(set.next(), Some(32));
+    }
+
+    #[test]
+    fn subtract_clears_bits_across_the_word_boundary() {
+        let mut lhs = BitSet::<u32>::new(64, true);
+        let mut rhs = BitSet::<u32>::new(64, false);
+        rhs.insert(31);
+        rhs.insert(32);
+
+        lhs.subtract(&rhs.data);
+
+        assert_eq!(lhs.len(), 62);
+        assert!(!lhs.iter_ones().any(|i| i == 31 || i == 32));
+        assert!(lhs.iter_ones().any(|i| i == 30));
+        assert!(lhs.iter_ones().any(|i| i == 33));
+    }
+
+    #[test]
+    fn new_with_fill_masks_bits_past_len() {
+        // 70 bits over u32 lanes leaves a partial last word (70 % 32 == 6);
+        // `new`'s low_mask must not spuriously set the 26 unused high bits
+        // past index 69 that the last word's remaining capacity covers.
+        let set = BitSet::<u32>::new(70, true);
+
+        assert_eq!(set.len(), 70);
+        assert_eq!(
+            set.iter_ones().collect::<Vec<_>>(),
+            (0..70).collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,156 +1,108 @@
-use std::{cell::RefCell, iter::zip, rc::Rc};
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
 
-use crate::Point;
+use crate::{
+    Point,
+    bitset::{BitMatrix, BitSet},
+    metric::{DistanceMetric, Euclidean, build_distance_matrix},
+};
 
-#[derive(Debug)]
-struct PointData {
-    location: Vec<Point>,
-    distance_matrix: Vec<Vec<f32>>,
-}
-
-impl PointData {
-    fn new(location_count: usize) -> Self {
-        Self {
-            location: vec![Point::default(); location_count],
-            distance_matrix: vec![vec![0.; location_count]; location_count],
-        }
-    }
-}
-
-struct AdjacencyMatrix {
-    data: Vec<PointVec>,
+/// Every finite pairwise distance, flattened into one ascending-sorted list
+/// of `(distance, i, j)` edges (`i < j`). The conflict graph at any
+/// `neighbour_distance` threshold is exactly the prefix of this list up to
+/// that distance, which is what lets `AdjacencyMatrix` update incrementally
+/// instead of rebuilding from the dense matrix on every bisection step.
+struct SortedEdges {
+    edges: Box<[(f32, usize, usize)]>,
 }
 
-impl AdjacencyMatrix {
-    fn new(location_count: usize, point_data: &PointData, neighbour_distance: f32) -> Self {
-        let mut ret = Self {
-            data: vec![PointVec::new(location_count, false); location_count],
-        };
+impl SortedEdges {
+    fn build(distance_matrix: &[Vec<f32>]) -> Self {
+        let mut edges = Vec::new();
 
-        for (index, row) in ret.data.iter_mut().enumerate() {
-            for (point, distance) in point_data.distance_matrix[index].iter().enumerate() {
-                if *distance <= neighbour_distance {
-                    row.insert(point);
+        for (i, row) in distance_matrix.iter().enumerate() {
+            for (j, &distance) in row.iter().enumerate().skip(i + 1) {
+                if distance.is_finite() {
+                    edges.push((distance, i, j));
                 }
             }
         }
+        edges.sort_by(|a, b| a.0.total_cmp(&b.0));
 
-        ret
+        Self {
+            edges: edges.into_boxed_slice(),
+        }
     }
 }
 
-#[derive(Clone)]
-struct PointVec {
-    data: Vec<u32>,
-    true_count: usize,
+/// The conflict graph, backed by [`BitMatrix`] with `u64` lanes (halving the
+/// word-level work of `u32` lanes on 64-bit-capable targets like WASM).
+struct AdjacencyMatrix {
+    rows: BitMatrix<u64>,
+    edge_count: usize,
 }
 
-impl PointVec {
-    fn new(location_count: usize, fill: bool) -> Self {
-        let sector_count = location_count / 32 + !location_count.is_multiple_of(32) as usize;
-        if fill {
-            let mut ret = Self {
-                data: vec![u32::MAX; sector_count],
-                true_count: location_count,
-            };
-
-            if !location_count.is_multiple_of(32) {
-                *ret.data.last_mut().unwrap() &= 2_u32.pow(location_count as u32 % 32) - 1;
-            }
-
-            ret
-        } else {
-            Self {
-                data: vec![0; sector_count],
-                true_count: 0,
-            }
+impl AdjacencyMatrix {
+    fn new(location_count: usize) -> Self {
+        Self {
+            rows: BitMatrix::new(location_count, location_count),
+            edge_count: 0,
         }
     }
 
-    fn reset(&mut self, location_count: usize, fill: bool) {
-        if fill {
-            for sector in self.data.iter_mut() {
-                *sector = u32::MAX;
-            }
-
-            if !location_count.is_multiple_of(32) {
-                *self.data.last_mut().unwrap() &= 2_u32.pow(location_count as u32 % 32) - 1;
-            }
-
-            self.true_count = location_count;
-        } else {
-            for sector in self.data.iter_mut() {
-                *sector = 0;
-            }
-            self.true_count = 0;
-        }
+    fn row(&self, index: usize) -> &[u64] {
+        self.rows.row(index)
     }
 
-    fn len(&self) -> usize {
-        self.true_count
-    }
+    /// Moves the conflict graph from its current threshold to
+    /// `neighbour_distance` by toggling only the edges that cross the
+    /// boundary between the two, rather than rebuilding every row: since
+    /// `edges` is sorted ascending, the conflicting pairs at any threshold
+    /// are always a prefix of it, so walking the edge pointer from the old
+    /// prefix length to the new one touches exactly the edges that changed.
+    fn advance_to(&mut self, edges: &[(f32, usize, usize)], neighbour_distance: f32) {
+        let target = edges.partition_point(|&(distance, ..)| distance <= neighbour_distance);
 
-    fn copy(&mut self, copy_src: &PointVec) {
-        for (lhs, rhs) in zip(self.data.iter_mut(), copy_src.data.iter()) {
-            *lhs = *rhs;
+        while self.edge_count < target {
+            let (_, i, j) = edges[self.edge_count];
+            self.rows.insert(i, j);
+            self.rows.insert(j, i);
+            self.edge_count += 1;
         }
-        self.true_count = copy_src.true_count;
-    }
 
-    fn next(&self) -> Option<usize> {
-        self.data
-            .iter()
-            .position(|value| *value != 0)
-            .map(|index| 32 * index + self.data[index].trailing_zeros() as usize)
-    }
-
-    fn subtract(&mut self, rhs: &PointVec) {
-        self.true_count = 0;
-        for (lhs, rhs) in zip(self.data.iter_mut(), rhs.data.iter()) {
-            *lhs &= !rhs;
-            self.true_count += lhs.count_ones() as usize;
+        while self.edge_count > target {
+            self.edge_count -= 1;
+            let (_, i, j) = edges[self.edge_count];
+            self.rows.remove(i, j);
+            self.rows.remove(j, i);
         }
     }
 
-    fn remove(&mut self, index: usize) {
-        let sector = index / 32;
-        let bitmask = 1 << (index % 32);
-
-        self.true_count -= (self.data[sector] & bitmask == bitmask) as usize;
-        self.data[sector] &= !bitmask;
-    }
-
-    fn insert(&mut self, index: usize) {
-        let sector = index / 32;
-        let bitmask = 1 << (index % 32);
-
-        self.true_count += (self.data[sector] & bitmask != bitmask) as usize;
-        self.data[sector] |= bitmask;
-    }
-}
+    /// Builds the conflict graph for a single required minimum separation in
+    /// one pass: two points conflict exactly when they're closer than
+    /// `min_distance`, so an independent set in the result is a selection
+    /// where every pair clears `min_distance` (the boundary is excluded from
+    /// conflicts, unlike `advance_to`'s thresholds, so that a pair exactly at
+    /// `min_distance` apart still counts as satisfying it).
+    fn for_min_distance(
+        location_count: usize,
+        edges: &[(f32, usize, usize)],
+        min_distance: f32,
+    ) -> Self {
+        let mut matrix = Self::new(location_count);
+        let edge_count = edges.partition_point(|&(distance, ..)| distance < min_distance);
 
-impl From<PointVec> for Box<[usize]> {
-    fn from(val: PointVec) -> Self {
-        let mut result: Vec<usize> = Vec::new();
-        for (index, value) in val
-            .data
-            .iter()
-            .enumerate()
-            .filter(|(_index, value)| **value != 0)
-        {
-            // copy out the u32 ref
-            let mut value = *value;
-
-            while value > 0 {
-                result.push(index * 32 + value.trailing_zeros() as usize);
-                value ^= 1 << value.trailing_zeros();
-            }
+        for &(_, i, j) in &edges[..edge_count] {
+            matrix.rows.insert(i, j);
+            matrix.rows.insert(j, i);
         }
+        matrix.edge_count = edge_count;
 
-        result.into_boxed_slice()
+        matrix
     }
 }
 
+type PointVec = BitSet<u64>;
+
 #[derive(Clone)]
 struct SolveData {
     selected_points: PointVec,
@@ -213,111 +165,919 @@ impl SolveStack {
     }
 }
 
-impl Point {
-    fn set(&mut self, point: &Point) {
-        self.x = point.x;
-        self.y = point.y;
+/// A valid selection is an independent set in `adjacency_matrix`, so no
+/// clique can contribute more than one more point to it: greedily partition
+/// `remaining_points` into cliques (pick the lowest remaining vertex, then
+/// keep folding in any remaining vertex adjacent to every vertex picked so
+/// far for that clique, via `BitSet::intersection`) and the clique count
+/// bounds how many more points `search` could still add.
+fn clique_partition_bound(remaining_points: &PointVec, adjacency_matrix: &AdjacencyMatrix) -> usize {
+    let mut working = remaining_points.clone();
+    let mut clique_count = 0;
+
+    while let Some(seed) = working.next() {
+        working.remove(seed);
+
+        let mut candidates = working.clone();
+        candidates.intersection(adjacency_matrix.row(seed));
+
+        while let Some(member) = candidates.next() {
+            candidates.remove(member);
+            working.remove(member);
+            candidates.intersection(adjacency_matrix.row(member));
+        }
+
+        clique_count += 1;
     }
 
-    fn get_distance(&self, point: &Point) -> f32 {
-        ((self.x - point.x).powi(2) + (self.y - point.y).powi(2)).sqrt()
+    clique_count
+}
+
+fn min_pairwise_distance(distance_matrix: &[Vec<f32>], indices: &[usize]) -> f32 {
+    let mut min_distance = f32::INFINITY;
+
+    for (position, &point_a) in indices.iter().enumerate() {
+        for &point_b in &indices[position + 1..] {
+            min_distance = min_distance.min(distance_matrix[point_a][point_b]);
+        }
     }
+
+    min_distance
 }
 
-fn search(
-    solve_data: Rc<RefCell<SolveData>>,
-    stack: &mut SolveStack,
-    adjacency_matrix: &AdjacencyMatrix,
-    select_size: usize,
-) -> Option<Rc<RefCell<SolveData>>> {
-    let mut mut_solve_data = solve_data.borrow_mut();
+/// Like [`min_pairwise_distance`], but reports a defined `0.0` rather than
+/// `f32::INFINITY` for a selection of fewer than two points: "no pair to
+/// violate a constraint" is meaningful internally (e.g. to
+/// `local_search_improve`'s critical-pair search), but `inf` isn't a usable
+/// "achieved dispersion" for a caller reporting or round-tripping it.
+///
+/// A selection of two or more points that's genuinely infinite (every pair
+/// unreachable under an obstacle-aware metric, per [`DistanceMetric`]'s
+/// contract) is left as `f32::INFINITY` rather than folded into that same
+/// `0.0` — it's the opposite situation (maximally, not minimally, dispersed)
+/// and collapsing the two would mislead a caller reading this as a quality
+/// signal.
+fn achieved_dispersion(distance_matrix: &[Vec<f32>], indices: &[usize]) -> f32 {
+    if indices.len() < 2 {
+        return 0.;
+    }
+
+    min_pairwise_distance(distance_matrix, indices)
+}
+
+/// Builds a strong feasible `placements`-sized selection up front: seed with
+/// the globally farthest pair, then repeatedly add the point farthest from
+/// the selection so far (maintaining `nearest_selected_distance[j]`, the
+/// ELBG-style reassignment-loop analogue of the greedy maximin heuristic),
+/// tracking the achieved dispersion as the running minimum of the distances
+/// at selection time.
+fn greedy_maximin_seed(distance_matrix: &[Vec<f32>], placements: usize) -> (Vec<usize>, f32) {
+    let n = distance_matrix.len();
+
+    let mut seed_a = 0;
+    let mut seed_b = 1;
+    let mut seed_distance = distance_matrix[0][1];
+
+    for (i, row) in distance_matrix.iter().enumerate() {
+        for (j, &distance) in row.iter().enumerate().skip(i + 1) {
+            if distance > seed_distance {
+                seed_distance = distance;
+                seed_a = i;
+                seed_b = j;
+            }
+        }
+    }
+
+    let mut selected = vec![seed_a, seed_b];
+    let mut nearest_selected_distance = vec![f32::INFINITY; n];
+    nearest_selected_distance[seed_a] = -1.;
+    nearest_selected_distance[seed_b] = -1.;
+
+    for (index, distance) in nearest_selected_distance.iter_mut().enumerate() {
+        if *distance < 0. {
+            continue;
+        }
+        *distance = distance_matrix[index][seed_a].min(distance_matrix[index][seed_b]);
+    }
+
+    let mut dispersion = seed_distance;
 
-    if mut_solve_data.selected_points.len() >= select_size {
-        drop(mut_solve_data);
-        return Some(solve_data);
+    while selected.len() < placements {
+        let next = nearest_selected_distance
+            .iter()
+            .enumerate()
+            .filter(|(_, distance)| **distance >= 0.)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+            .expect("placements <= n, so an unselected point must remain");
+
+        dispersion = dispersion.min(nearest_selected_distance[next]);
+        selected.push(next);
+        nearest_selected_distance[next] = -1.;
+
+        for (index, distance) in nearest_selected_distance.iter_mut().enumerate() {
+            if *distance < 0. {
+                continue;
+            }
+            *distance = distance.min(distance_matrix[index][next]);
+        }
     }
 
-    if mut_solve_data.remaining_points.len() < select_size - mut_solve_data.selected_points.len() {
-        return None;
+    (selected, dispersion)
+}
+
+/// Repeatedly finds the critical (minimum-distance) selected pair and tries
+/// swapping one of its endpoints for an unselected point that raises the
+/// current minimum pairwise distance, until no such swap is left. Returns
+/// the dispersion achieved by `selected` once it converges.
+fn local_search_improve(distance_matrix: &[Vec<f32>], selected: &mut [usize]) -> f32 {
+    let n = distance_matrix.len();
+    let p = selected.len();
+
+    loop {
+        let mut critical_a = 0;
+        let mut critical_b = 1;
+        let mut critical_distance = f32::INFINITY;
+
+        for i in 0..p {
+            for j in (i + 1)..p {
+                let distance = distance_matrix[selected[i]][selected[j]];
+                if distance < critical_distance {
+                    critical_distance = distance;
+                    critical_a = i;
+                    critical_b = j;
+                }
+            }
+        }
+
+        let in_selection: HashSet<usize> = selected.iter().copied().collect();
+        let mut improved = false;
+
+        'find_swap: for endpoint in [critical_a, critical_b] {
+            for candidate in 0..n {
+                if in_selection.contains(&candidate) {
+                    continue;
+                }
+
+                let previous = selected[endpoint];
+                selected[endpoint] = candidate;
+
+                if min_pairwise_distance(distance_matrix, selected) > critical_distance {
+                    improved = true;
+                    break 'find_swap;
+                }
+
+                selected[endpoint] = previous;
+            }
+        }
+
+        if !improved {
+            return critical_distance;
+        }
     }
+}
 
-    // pick next point
-    let point = mut_solve_data
-        .remaining_points
-        .next()
-        .expect("At least one point remaining");
-    mut_solve_data.remaining_points.remove(point);
+/// Finds `placements` points maximizing the minimum pairwise distance among
+/// them, returning the selected indices alongside the dispersion they
+/// achieve. See [`p_solver_max_placements`] for the dual query: a required
+/// separation instead of a fixed placement count.
+pub fn p_solver(input_data: &[Point], placements: u32) -> Option<(Box<[usize]>, f32)> {
+    p_solver_with_metric(input_data, placements, &Euclidean)
+}
 
-    // Pick this point
-    let new_data = stack.alloc();
-    let mut mut_new_data = new_data.borrow_mut();
-    mut_new_data.copy(&mut_solve_data);
+/// Same as [`p_solver`], but lets the caller pick how distance between two
+/// points is measured instead of assuming straight-line Euclidean distance.
+/// A metric may report `f32::INFINITY` for a pair that cannot reach each
+/// other at all (e.g. blocked by an obstacle); such a pair is simply never
+/// treated as conflicting.
+pub fn p_solver_with_metric(
+    input_data: &[Point],
+    placements: u32,
+    metric: &dyn DistanceMetric,
+) -> Option<(Box<[usize]>, f32)> {
+    let distance_matrix = build_distance_matrix(input_data, metric);
 
-    mut_new_data.selected_points.insert(point);
-    mut_new_data
-        .remaining_points
-        .subtract(&adjacency_matrix.data[point]);
+    p_solver_from_matrix(distance_matrix, placements)
+}
 
-    drop(mut_new_data);
+/// Same as [`p_solver`], but takes an already-computed distance matrix
+/// directly rather than deriving one from [`Point`] coordinates. This is how
+/// a caller feeds in geodesic/shortest-path distances around obstacles that
+/// no simple metric over coordinates could express. `distance_matrix` must
+/// be symmetric (`distance_matrix[i][j] == distance_matrix[j][i]`): the
+/// conflict graph is built from only the upper triangle and mirrored, so an
+/// asymmetric matrix would silently lose whichever direction it didn't read.
+pub fn p_solver_from_matrix(
+    distance_matrix: Vec<Vec<f32>>,
+    placements: u32,
+) -> Option<(Box<[usize]>, f32)> {
+    let mut session = PSolverSession::new_from_matrix(distance_matrix, placements);
 
-    if let Some(result) = search(new_data, stack, adjacency_matrix, select_size) {
-        return Some(result);
-    };
+    while session.step(usize::MAX) == StepOutcome::InProgress {}
 
-    stack.dealloc();
-    drop(mut_solve_data);
+    session.best_result()
+}
 
-    // Do not pick this point
-    search(solve_data, stack, adjacency_matrix, select_size)
+/// Finds the maximum number of points that can be placed with every pair at
+/// least `min_distance` apart, returning the selected indices alongside the
+/// dispersion actually achieved (which may exceed `min_distance`). This is
+/// the dual of [`p_solver`]: a required separation instead of a fixed
+/// placement count, which is the natural query for a facility-siting caller
+/// with a hard minimum-separation constraint rather than a fixed budget.
+pub fn p_solver_max_placements(input_data: &[Point], min_distance: f32) -> Option<(Box<[usize]>, f32)> {
+    p_solver_max_placements_with_metric(input_data, min_distance, &Euclidean)
 }
 
-pub fn p_solver(input_data: &[Point], placements: u32) -> Option<Box<[usize]>> {
-    let input_size = input_data.len();
-    let mut point_data = PointData::new(input_data.len());
+/// Same as [`p_solver_max_placements`], but lets the caller pick the
+/// distance metric, as in [`p_solver_with_metric`].
+pub fn p_solver_max_placements_with_metric(
+    input_data: &[Point],
+    min_distance: f32,
+    metric: &dyn DistanceMetric,
+) -> Option<(Box<[usize]>, f32)> {
+    let distance_matrix = build_distance_matrix(input_data, metric);
+
+    p_solver_max_placements_from_matrix(distance_matrix, min_distance)
+}
+
+/// Same as [`p_solver_max_placements`], but takes an already-computed
+/// distance matrix, as in [`p_solver_from_matrix`]. `distance_matrix` must
+/// be symmetric for the same reason [`p_solver_from_matrix`] requires it.
+pub fn p_solver_max_placements_from_matrix(
+    distance_matrix: Vec<Vec<f32>>,
+    min_distance: f32,
+) -> Option<(Box<[usize]>, f32)> {
+    let mut session = MaxPlacementsSession::new_from_matrix(distance_matrix, min_distance);
 
-    for (point_input, point_data) in zip(input_data, point_data.location.iter_mut()) {
-        point_data.set(point_input);
+    while session.step(usize::MAX) == StepOutcome::InProgress {}
+
+    session.best_result()
+}
+
+/// What a single [`PSolverSession::step`] slice accomplished.
+#[derive(Debug, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// The bisection hasn't converged yet; keep calling `step`.
+    InProgress,
+    /// The bisection has converged; [`PSolverSession::best_result`] is final.
+    Done,
+}
+
+/// The outcome of proving (or disproving, or not yet finishing proving)
+/// feasibility at a single bisection threshold.
+enum SearchOutcome {
+    Feasible,
+    Infeasible,
+    BudgetExhausted,
+}
+
+/// A paused exact-search bisection: `p_solver`'s search, but driven one
+/// bounded `step` at a time instead of run to completion in one call, so a
+/// caller (e.g. WASM, where a long exact run would otherwise block the main
+/// thread) can yield between slices and inspect the best-known-feasible
+/// placement in the meantime. Resuming is simply calling `step` again — all
+/// of the in-progress state (bisection window, conflict graph, best result
+/// so far) lives on the session.
+pub struct PSolverSession {
+    distance_matrix: Vec<Vec<f32>>,
+    edges: SortedEdges,
+    possible_point_distance: Vec<f32>,
+    left_index: usize,
+    right_index: usize,
+    initial_span: usize,
+    select_size: usize,
+    input_size: usize,
+    best_result: PointVec,
+    stack: SolveStack,
+    adjacency_matrix: AdjacencyMatrix,
+    nodes_explored: u64,
+    /// The bisection threshold currently being proven, if a `step` call has
+    /// paused mid-proof. `None` means `stack` holds no in-progress search and
+    /// the next `step` should start a fresh threshold.
+    current_target: Option<usize>,
+}
+
+impl PSolverSession {
+    /// Same as [`p_solver`], but returns a paused session instead of
+    /// solving to completion.
+    pub fn new(input_data: &[Point], placements: u32) -> Self {
+        Self::new_with_metric(input_data, placements, &Euclidean)
     }
 
-    for (point_a, row) in point_data.distance_matrix.iter_mut().enumerate() {
-        for (point_b, distance) in row.iter_mut().enumerate() {
-            *distance = point_data.location[point_a].get_distance(&point_data.location[point_b]);
+    /// Same as [`PSolverSession::new`], but lets the caller pick the
+    /// distance metric, as in [`p_solver_with_metric`].
+    pub fn new_with_metric(
+        input_data: &[Point],
+        placements: u32,
+        metric: &dyn DistanceMetric,
+    ) -> Self {
+        Self::new_from_matrix(build_distance_matrix(input_data, metric), placements)
+    }
+
+    /// Same as [`PSolverSession::new`], but takes an already-computed
+    /// distance matrix, as in [`p_solver_from_matrix`].
+    pub fn new_from_matrix(distance_matrix: Vec<Vec<f32>>, placements: u32) -> Self {
+        let input_size = distance_matrix.len();
+
+        let edges = SortedEdges::build(&distance_matrix);
+        let mut possible_point_distance: Vec<f32> =
+            edges.edges.iter().map(|&(distance, ..)| distance).collect();
+        possible_point_distance.dedup();
+        if possible_point_distance.is_empty() {
+            // No two points share a finite distance (e.g. a single point, or
+            // every pair blocked), so no threshold ever conflicts anything;
+            // any placeholder value keeps the bisection below well-defined.
+            possible_point_distance.push(0.);
+        }
+
+        // Seed the search with a fast heuristic placement instead of
+        // starting from scratch: any threshold below its achieved
+        // dispersion is trivially feasible, so the exact search only has to
+        // prove optimality above it, and the heuristic selection itself
+        // becomes the anytime result if nothing later beats it.
+        let mut left_index = 0;
+        let mut best_result = PointVec::new(input_size, false);
+        let select_size = placements as usize;
+
+        if select_size <= input_size {
+            let (selected, dispersion): (Vec<usize>, f32) = if select_size >= 2 {
+                let (mut selected, _) = greedy_maximin_seed(&distance_matrix, select_size);
+                let dispersion = local_search_improve(&distance_matrix, &mut selected);
+                (selected, dispersion)
+            } else {
+                // Fewer than 2 points can never conflict, so any
+                // `select_size`-sized subset (there's always one, since
+                // `select_size <= input_size`) is already an optimal
+                // selection; skip straight to it instead of relying on
+                // `greedy_maximin_seed`, which assumes at least 2 points to
+                // seed from.
+                ((0..select_size).collect(), f32::INFINITY)
+            };
+
+            left_index = possible_point_distance
+                .partition_point(|&d| d <= dispersion)
+                .saturating_sub(1);
+
+            for point in selected {
+                best_result.insert(point);
+            }
+        }
+
+        let right_index = possible_point_distance.len() - 1;
+
+        Self {
+            distance_matrix,
+            edges,
+            possible_point_distance,
+            initial_span: right_index.saturating_sub(left_index),
+            left_index,
+            right_index,
+            select_size,
+            input_size,
+            best_result,
+            stack: SolveStack::new(input_size, input_size),
+            adjacency_matrix: AdjacencyMatrix::new(input_size),
+            nodes_explored: 0,
+            current_target: None,
         }
     }
 
-    let mut possible_point_distance = point_data
-        .distance_matrix
-        .first()
-        .expect("Distance matrix must not be empty")
-        .clone();
-    possible_point_distance.sort_by(f32::total_cmp);
+    /// Advances the current bisection threshold's branch-and-bound proof by
+    /// at most `budget` nodes of the DFS living in `self.stack`, stopping as
+    /// soon as the proof concludes or the budget runs out. Backtracking here
+    /// is just "dealloc the top frame and loop again" rather than a function
+    /// return, so a paused proof costs nothing to resume: the next call just
+    /// keeps looping from whatever frame `self.stack` was left at.
+    fn advance(&mut self, budget: &mut usize) -> SearchOutcome {
+        loop {
+            if self.stack.idx == 0 {
+                return SearchOutcome::Infeasible;
+            }
+
+            if *budget == 0 {
+                return SearchOutcome::BudgetExhausted;
+            }
+            *budget -= 1;
+
+            let current = self.stack.data[self.stack.idx - 1].clone();
+            let mut data = current.borrow_mut();
+
+            if data.selected_points.len() >= self.select_size {
+                let selected = data.selected_points.clone();
+                drop(data);
+                self.best_result.copy(&selected);
+                return SearchOutcome::Feasible;
+            }
+
+            if data.remaining_points.len() < self.select_size - data.selected_points.len() {
+                drop(data);
+                self.stack.dealloc();
+                continue;
+            }
+
+            let clique_count =
+                clique_partition_bound(&data.remaining_points, &self.adjacency_matrix);
+            if data.selected_points.len() + clique_count < self.select_size {
+                drop(data);
+                self.stack.dealloc();
+                continue;
+            }
 
-    let mut left_index = 0;
-    let mut right_index = possible_point_distance.len() - 1;
-    let mut best_result = PointVec::new(input_size, false);
-    let mut stack = SolveStack::new(input_size, input_size);
+            // pick next point
+            let point = data
+                .remaining_points
+                .next()
+                .expect("At least one point remaining");
+            data.remaining_points.remove(point);
 
-    while left_index < right_index {
+            // Pick this point
+            let new_data = self.stack.alloc();
+            let mut new_data = new_data.borrow_mut();
+            new_data.copy(&data);
+            new_data.selected_points.insert(point);
+            new_data
+                .remaining_points
+                .subtract(self.adjacency_matrix.row(point));
+
+            // Not picking this point is handled by simply looping back
+            // around to this same frame once its "pick" subtree is
+            // exhausted: `point` has already been removed from its
+            // `remaining_points` above.
+        }
+    }
+
+    /// Runs the current bisection threshold's proof for at most
+    /// `node_budget` nodes, then returns. A slice that exhausts its budget
+    /// leaves the search exactly where it stopped, so the next `step` call
+    /// resumes the same proof instead of restarting it.
+    pub fn step(&mut self, node_budget: usize) -> StepOutcome {
+        if self.left_index >= self.right_index {
+            return StepOutcome::Done;
+        }
+
+        let target = match self.current_target {
+            Some(target) => target,
+            None => {
+                let target = self.left_index.midpoint(self.right_index);
+
+                self.stack.reset(self.input_size);
+                self.stack.alloc();
+                self.adjacency_matrix
+                    .advance_to(&self.edges.edges, self.possible_point_distance[target]);
+                self.current_target = Some(target);
+
+                target
+            }
+        };
+
+        let mut budget = node_budget;
+        let outcome = self.advance(&mut budget);
+        self.nodes_explored += (node_budget - budget) as u64;
+
+        match outcome {
+            SearchOutcome::Feasible => {
+                self.left_index = target + 1;
+                self.current_target = None;
+            }
+            SearchOutcome::Infeasible => {
+                self.right_index = target;
+                self.current_target = None;
+            }
+            SearchOutcome::BudgetExhausted => {}
+        }
+
+        if self.left_index >= self.right_index {
+            StepOutcome::Done
+        } else {
+            StepOutcome::InProgress
+        }
+    }
+
+    /// The best feasible placement found so far, alongside the dispersion it
+    /// achieves: the greedy heuristic's selection until the exact search
+    /// proves something better, and the true optimum once the session is
+    /// done.
+    pub fn best_result(&self) -> Option<(Box<[usize]>, f32)> {
+        // An empty `best_result` is only the "nothing found yet" sentinel
+        // when `select_size > 0`; selecting zero points is itself the
+        // trivially-correct answer for `select_size == 0`; and it's the one
+        // PointVec::len() can never distinguish from "not found" on its own.
+        if self.select_size > 0 && self.best_result.len() == 0 {
+            None
+        } else {
+            let indices: Box<[usize]> = self.best_result.clone().into();
+            let objective = achieved_dispersion(&self.distance_matrix, &indices);
+
+            Some((indices, objective))
+        }
+    }
+
+    /// A coarse `0.0..=1.0` estimate of how much of the bisection window has
+    /// collapsed so far. Not proportional to wall-clock work — later slices
+    /// tend to explore far larger subtrees than earlier ones — but cheap
+    /// enough to report every slice.
+    pub fn progress(&self) -> f32 {
+        if self.initial_span == 0 {
+            1.
+        } else {
+            1. - (self.right_index - self.left_index) as f32 / self.initial_span as f32
+        }
+    }
+
+    pub fn nodes_explored(&self) -> u64 {
+        self.nodes_explored
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.left_index >= self.right_index
+    }
+}
+
+/// A paused branch-and-bound maximum independent set search against a
+/// single, fixed conflict graph: the dual of [`PSolverSession`] (a required
+/// separation instead of a fixed placement count), driven one bounded
+/// [`MaxPlacementsSession::step`] slice at a time for the same reason
+/// `PSolverSession` is — a long exact run shouldn't block the caller's main
+/// thread. Unlike `PSolverSession`'s decision search (stop as soon as
+/// `select_size` points are found), this explores every branch the
+/// clique-partition bound can't rule out and keeps whichever leaf selects the
+/// most points, so there's no bisection window to collapse, just one DFS to
+/// resume.
+pub struct MaxPlacementsSession {
+    distance_matrix: Vec<Vec<f32>>,
+    adjacency_matrix: AdjacencyMatrix,
+    stack: SolveStack,
+    best: PointVec,
+    nodes_explored: u64,
+    done: bool,
+}
+
+impl MaxPlacementsSession {
+    /// Same as [`p_solver_max_placements`], but returns a paused session
+    /// instead of solving to completion.
+    pub fn new(input_data: &[Point], min_distance: f32) -> Self {
+        Self::new_with_metric(input_data, min_distance, &Euclidean)
+    }
+
+    /// Same as [`MaxPlacementsSession::new`], but lets the caller pick the
+    /// distance metric, as in [`p_solver_max_placements_with_metric`].
+    pub fn new_with_metric(
+        input_data: &[Point],
+        min_distance: f32,
+        metric: &dyn DistanceMetric,
+    ) -> Self {
+        Self::new_from_matrix(build_distance_matrix(input_data, metric), min_distance)
+    }
+
+    /// Same as [`MaxPlacementsSession::new`], but takes an already-computed
+    /// distance matrix, as in [`p_solver_max_placements_from_matrix`].
+    pub fn new_from_matrix(distance_matrix: Vec<Vec<f32>>, min_distance: f32) -> Self {
+        let input_size = distance_matrix.len();
+
+        let edges = SortedEdges::build(&distance_matrix);
+        let adjacency_matrix =
+            AdjacencyMatrix::for_min_distance(input_size, &edges.edges, min_distance);
+
+        // Unlike the primal, this search has no warm start to keep its
+        // achieved depth well below `input_size`: an empty (or sparse)
+        // conflict graph makes every point mutually independent, so the DFS
+        // can legitimately select all `input_size` of them, one stack frame
+        // per pick plus the root frame.
+        let mut stack = SolveStack::new(input_size + 1, input_size);
         stack.reset(input_size);
-        let target = left_index.midpoint(right_index);
-
-        match search(
-            stack.alloc(),
-            &mut stack,
-            &AdjacencyMatrix::new(input_size, &point_data, possible_point_distance[target]),
-            placements as usize,
-        ) {
-            Some(result) => {
-                left_index = target + 1;
-                best_result.copy(&result.borrow().selected_points);
+        if input_size > 0 {
+            stack.alloc();
+        }
+
+        Self {
+            distance_matrix,
+            adjacency_matrix,
+            stack,
+            best: PointVec::new(input_size, false),
+            nodes_explored: 0,
+            done: input_size == 0,
+        }
+    }
+
+    /// Advances the search by at most `node_budget` nodes, stopping as soon
+    /// as every branch has been explored or ruled out, or the budget runs
+    /// out. Mirrors [`PSolverSession::advance`]: "not picking this point" is
+    /// handled by looping back around to the same frame once its "pick"
+    /// subtree is exhausted, rather than an explicit second recursive call,
+    /// so a paused search costs nothing to resume.
+    pub fn step(&mut self, node_budget: usize) -> StepOutcome {
+        if self.done {
+            return StepOutcome::Done;
+        }
+
+        let mut budget = node_budget;
+
+        loop {
+            if self.stack.idx == 0 {
+                self.done = true;
+                break;
+            }
+
+            if budget == 0 {
+                break;
             }
-            None => right_index = target,
+            budget -= 1;
+
+            let current = self.stack.data[self.stack.idx - 1].clone();
+            let mut data = current.borrow_mut();
+
+            if data.remaining_points.len() == 0 {
+                if data.selected_points.len() > self.best.len() {
+                    self.best.copy(&data.selected_points);
+                }
+                drop(data);
+                self.stack.dealloc();
+                continue;
+            }
+
+            let clique_count =
+                clique_partition_bound(&data.remaining_points, &self.adjacency_matrix);
+            if data.selected_points.len() + clique_count <= self.best.len() {
+                drop(data);
+                self.stack.dealloc();
+                continue;
+            }
+
+            // pick next point
+            let point = data
+                .remaining_points
+                .next()
+                .expect("At least one point remaining");
+            data.remaining_points.remove(point);
+
+            // Include this point
+            let new_data = self.stack.alloc();
+            let mut new_data = new_data.borrow_mut();
+            new_data.copy(&data);
+            new_data.selected_points.insert(point);
+            new_data
+                .remaining_points
+                .subtract(self.adjacency_matrix.row(point));
+
+            // Excluding this point is handled by simply looping back around
+            // to this same frame once its "pick" subtree is exhausted:
+            // `point` has already been removed from its `remaining_points`
+            // above.
+        }
+
+        self.nodes_explored += (node_budget - budget) as u64;
+
+        if self.done {
+            StepOutcome::Done
+        } else {
+            StepOutcome::InProgress
         }
     }
 
-    if best_result.true_count == 0 {
-        None
-    } else {
-        Some(best_result.into())
+    /// The best feasible placement found so far, alongside the dispersion it
+    /// achieves: `None` until the search has found at least one feasible
+    /// placement, and the true optimum once the session is done.
+    pub fn best_result(&self) -> Option<(Box<[usize]>, f32)> {
+        if self.best.len() == 0 {
+            None
+        } else {
+            let indices: Box<[usize]> = self.best.clone().into();
+            let objective = achieved_dispersion(&self.distance_matrix, &indices);
+
+            Some((indices, objective))
+        }
+    }
+
+    pub fn nodes_explored(&self) -> u64 {
+        self.nodes_explored
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal xorshift PRNG so these tests stay deterministic without
+    /// pulling in an external `rand` dependency, same rationale as
+    /// `annealing::Xorshift64`.
+    struct Xorshift32 {
+        state: u32,
+    }
+
+    impl Xorshift32 {
+        fn new(seed: u32) -> Self {
+            Self {
+                state: if seed == 0 { 0x9e3779b9 } else { seed },
+            }
+        }
+
+        fn next_u32(&mut self) -> u32 {
+            let mut x = self.state;
+            x ^= x << 13;
+            x ^= x >> 17;
+            x ^= x << 5;
+            self.state = x;
+            x
+        }
+
+        fn next_unit(&mut self) -> f32 {
+            (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+        }
+    }
+
+    fn random_points(rng: &mut Xorshift32, n: usize) -> Vec<Point> {
+        (0..n)
+            .map(|_| Point::new(rng.next_unit() * 10., rng.next_unit() * 10.))
+            .collect()
+    }
+
+    fn min_pairwise(points: &[Point], indices: &[usize]) -> f32 {
+        let mut min_distance = f32::INFINITY;
+        for (position, &a) in indices.iter().enumerate() {
+            for &b in &indices[position + 1..] {
+                min_distance = min_distance.min(points[a].get_distance(&points[b]));
+            }
+        }
+        min_distance
+    }
+
+    /// Exhaustively tries every `select_size`-sized subset of `points` and
+    /// returns the best achievable minimum pairwise distance: the ground
+    /// truth `p_solver`'s branch-and-bound is supposed to match exactly.
+    fn brute_force_dispersion(points: &[Point], select_size: usize) -> Option<f32> {
+        let n = points.len();
+        if select_size > n {
+            return None;
+        }
+        if select_size < 2 {
+            return Some(0.);
+        }
+
+        let mut best: Option<f32> = None;
+        for mask in 0u32..(1 << n) {
+            if mask.count_ones() as usize != select_size {
+                continue;
+            }
+            let indices: Vec<usize> = (0..n).filter(|i| mask & (1 << i) != 0).collect();
+            let dispersion = min_pairwise(points, &indices);
+            best = Some(best.map_or(dispersion, |b: f32| b.max(dispersion)));
+        }
+        best
+    }
+
+    /// Exhaustively tries every subset of `points` and returns the largest
+    /// size whose pairwise distances are all at least `min_distance`: the
+    /// ground truth `p_solver_max_placements` is supposed to match exactly.
+    fn brute_force_max_placements(points: &[Point], min_distance: f32) -> usize {
+        let n = points.len();
+        let mut best = 0;
+        for mask in 0u32..(1 << n) {
+            let indices: Vec<usize> = (0..n).filter(|i| mask & (1 << i) != 0).collect();
+            if indices.len() <= best {
+                continue;
+            }
+            if min_pairwise(points, &indices) >= min_distance {
+                best = indices.len();
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn p_solver_matches_brute_force() {
+        let mut rng = Xorshift32::new(0xc0ffee);
+
+        for trial in 0..20 {
+            let n = 2 + (trial % 6);
+            let points = random_points(&mut rng, n);
+
+            for placements in 1..=n {
+                let expected = brute_force_dispersion(&points, placements);
+                let got = p_solver(&points, placements as u32);
+
+                match (got, expected) {
+                    (Some((indices, dispersion)), Some(expected_dispersion)) => {
+                        assert_eq!(indices.len(), placements);
+                        if placements >= 2 {
+                            assert!(
+                                (dispersion - expected_dispersion).abs() < 1e-3,
+                                "trial {trial}, n {n}, placements {placements}: got {dispersion}, expected {expected_dispersion}"
+                            );
+                        }
+                    }
+                    (None, None) => {}
+                    (got, expected) => panic!(
+                        "trial {trial}, n {n}, placements {placements}: got {got:?}, expected {expected:?}"
+                    ),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn p_solver_empty_selection_is_trivially_found() {
+        let points = vec![Point::new(0., 0.), Point::new(1., 1.)];
+        let (indices, dispersion) =
+            p_solver(&points, 0).expect("an empty selection is always feasible");
+
+        assert!(indices.is_empty());
+        assert_eq!(dispersion, 0.);
+    }
+
+    #[test]
+    fn p_solver_max_placements_matches_brute_force() {
+        let mut rng = Xorshift32::new(0xfeedface);
+
+        for trial in 0..20 {
+            let n = 2 + (trial % 6);
+            let points = random_points(&mut rng, n);
+
+            let mut thresholds: Vec<f32> = (0..n)
+                .flat_map(|a| (a + 1..n).map(move |b| (a, b)))
+                .map(|(a, b)| points[a].get_distance(&points[b]))
+                .collect();
+            thresholds.push(0.);
+
+            for &min_distance in &thresholds {
+                let expected = brute_force_max_placements(&points, min_distance);
+                let (indices, _) = p_solver_max_placements(&points, min_distance)
+                    .expect("at least a single point is always a feasible placement");
+
+                assert_eq!(
+                    indices.len(),
+                    expected,
+                    "trial {trial}, n {n}, min_distance {min_distance}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn p_solver_session_resumes_across_small_budgets() {
+        let mut rng = Xorshift32::new(0x5eed);
+
+        for trial in 0..10 {
+            let n = 3 + (trial % 5);
+            let points = random_points(&mut rng, n);
+            let placements = 2 + (trial % (n - 1));
+
+            let mut session = PSolverSession::new(&points, placements as u32);
+            while session.step(1) == StepOutcome::InProgress {
+                let progress = session.progress();
+                assert!((0. ..=1.).contains(&progress));
+            }
+            assert!(session.is_done());
+
+            let (resumed_indices, resumed_dispersion) = session
+                .best_result()
+                .expect("a 2..n-sized selection is always feasible");
+            let (full_indices, full_dispersion) = p_solver(&points, placements as u32)
+                .expect("a 2..n-sized selection is always feasible");
+
+            assert_eq!(resumed_indices.len(), full_indices.len());
+            assert!(
+                (resumed_dispersion - full_dispersion).abs() < 1e-3,
+                "trial {trial}, n {n}, placements {placements}: resumed {resumed_dispersion}, full {full_dispersion}"
+            );
+        }
+    }
+
+    #[test]
+    fn max_placements_session_resumes_across_small_budgets() {
+        let mut rng = Xorshift32::new(0xc0de);
+
+        for trial in 0..10 {
+            let n = 2 + (trial % 6);
+            let points = random_points(&mut rng, n);
+            let min_distance = if n >= 2 {
+                points[0].get_distance(&points[1]) * 0.5
+            } else {
+                0.
+            };
+
+            let mut session = MaxPlacementsSession::new(&points, min_distance);
+            let mut nodes_before = 0;
+            while session.step(1) == StepOutcome::InProgress {
+                assert!(session.nodes_explored() >= nodes_before);
+                nodes_before = session.nodes_explored();
+            }
+            assert!(session.is_done());
+
+            let (resumed_indices, resumed_dispersion) = session
+                .best_result()
+                .expect("at least a single point is always a feasible placement");
+            let (full_indices, full_dispersion) = p_solver_max_placements(&points, min_distance)
+                .expect("at least a single point is always a feasible placement");
+
+            assert_eq!(resumed_indices.len(), full_indices.len());
+            assert!(
+                (resumed_dispersion - full_dispersion).abs() < 1e-3,
+                "trial {trial}, n {n}, min_distance {min_distance}: resumed {resumed_dispersion}, full {full_dispersion}"
+            );
+        }
     }
 }
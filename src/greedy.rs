@@ -0,0 +1,133 @@
+/// Gonzalez's farthest-first traversal: a 2-approximation for p-dispersion.
+/// Starts from the first point, then repeatedly adds the point whose minimum
+/// distance to the already-selected set is largest, maintaining
+/// `dist_to_set[i]` so each pick costs `O(n)` instead of `O(n * p)`.
+///
+/// Takes an already-computed distance matrix rather than raw points, as
+/// `core.rs`'s `greedy_maximin_seed` does, so a caller under a non-Euclidean
+/// or obstacle-aware metric gets a seed selection consistent with the matrix
+/// the exact solver actually bisects over.
+///
+/// Clamps `p` to `distance_matrix.len()` rather than erroring, since the
+/// result is meant to seed other solvers, not to be a user-facing answer on
+/// its own.
+pub fn greedy_dispersion(distance_matrix: &[Vec<f32>], p: usize) -> Box<[usize]> {
+    let n = distance_matrix.len();
+    let target = p.min(n);
+
+    let mut selected = Vec::with_capacity(target);
+    if target == 0 {
+        return selected.into_boxed_slice();
+    }
+
+    let mut dist_to_set = vec![f32::INFINITY; n];
+    selected.push(0);
+    dist_to_set[0] = -1.;
+
+    while selected.len() < target {
+        let last = *selected.last().unwrap();
+
+        for (index, slot) in dist_to_set.iter_mut().enumerate() {
+            if *slot < 0. {
+                continue;
+            }
+            *slot = slot.min(distance_matrix[index][last]);
+        }
+
+        let next = dist_to_set
+            .iter()
+            .enumerate()
+            .filter(|(_, dist)| **dist >= 0.)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| index)
+            .expect("target <= n, so an unselected point must remain");
+
+        selected.push(next);
+        dist_to_set[next] = -1.;
+    }
+
+    selected.into_boxed_slice()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square_matrix(points: &[(f32, f32)]) -> Vec<Vec<f32>> {
+        points
+            .iter()
+            .map(|&(ax, ay)| {
+                points
+                    .iter()
+                    .map(|&(bx, by)| ((ax - bx).powi(2) + (ay - by).powi(2)).sqrt())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn min_pairwise(distance_matrix: &[Vec<f32>], indices: &[usize]) -> f32 {
+        let mut min_distance = f32::INFINITY;
+        for (position, &a) in indices.iter().enumerate() {
+            for &b in &indices[position + 1..] {
+                min_distance = min_distance.min(distance_matrix[a][b]);
+            }
+        }
+        min_distance
+    }
+
+    fn brute_force_dispersion(distance_matrix: &[Vec<f32>], select_size: usize) -> f32 {
+        let n = distance_matrix.len();
+        let mut best = f32::NEG_INFINITY;
+
+        for mask in 0u32..(1 << n) {
+            if mask.count_ones() as usize != select_size {
+                continue;
+            }
+            let indices: Vec<usize> = (0..n).filter(|i| mask & (1 << i) != 0).collect();
+            best = best.max(min_pairwise(distance_matrix, &indices));
+        }
+
+        best
+    }
+
+    #[test]
+    fn selects_target_distinct_points() {
+        let matrix = square_matrix(&[(0., 0.), (1., 0.), (0., 5.), (5., 5.), (5., 0.)]);
+        let selected = greedy_dispersion(&matrix, 3);
+
+        assert_eq!(selected.len(), 3);
+        let distinct: std::collections::HashSet<_> = selected.iter().collect();
+        assert_eq!(distinct.len(), 3);
+    }
+
+    #[test]
+    fn clamps_p_to_the_matrix_size() {
+        let matrix = square_matrix(&[(0., 0.), (1., 0.), (0., 5.)]);
+        let selected = greedy_dispersion(&matrix, 10);
+
+        assert_eq!(selected.len(), 3);
+    }
+
+    #[test]
+    fn is_within_the_2_approximation_bound_of_the_optimum() {
+        let matrix = square_matrix(&[
+            (0., 0.),
+            (1., 0.),
+            (0., 5.),
+            (5., 5.),
+            (5., 0.),
+            (2., 2.),
+        ]);
+
+        for p in 2..matrix.len() {
+            let optimum = brute_force_dispersion(&matrix, p);
+            let selected = greedy_dispersion(&matrix, p);
+            let achieved = min_pairwise(&matrix, &selected);
+
+            assert!(
+                achieved >= optimum / 2.,
+                "p {p}: achieved {achieved} fell below half the optimum {optimum}"
+            );
+        }
+    }
+}
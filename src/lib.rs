@@ -1,5 +1,25 @@
-use core::fmt;
-use std::{collections::HashSet, iter::zip};
+use std::{collections::HashSet, fmt};
+
+mod annealing;
+mod bitset;
+mod core;
+mod greedy;
+mod metric;
+mod naive;
+mod svg;
+
+pub use annealing::solve_p_dispersion_annealing;
+pub use core::{
+    MaxPlacementsSession, PSolverSession, StepOutcome, p_solver, p_solver_from_matrix,
+    p_solver_max_placements, p_solver_max_placements_from_matrix,
+    p_solver_max_placements_with_metric, p_solver_with_metric,
+};
+pub use greedy::greedy_dispersion;
+pub use metric::{DistanceMetric, Euclidean, Manhattan, SquaredEuclidean};
+pub use naive::naive_solver;
+pub use svg::{export_svg, export_svg_to_file};
+
+use metric::build_distance_matrix;
 
 #[derive(Default, Debug, Clone, Copy)]
 pub struct Point {
@@ -26,31 +46,11 @@ impl Point {
         Point { x, y }
     }
 
-    fn set(&mut self, point: &Point) {
-        self.x = point.x;
-        self.y = point.y;
-    }
-
     fn get_distance(&self, point: &Point) -> f32 {
         ((self.x - point.x).powi(2) + (self.y - point.y).powi(2)).sqrt()
     }
 }
 
-#[derive(Debug)]
-struct PointData {
-    location: Vec<Point>,
-    distance_matrix: Vec<Vec<f32>>,
-}
-
-impl PointData {
-    fn new(location_count: usize) -> Self {
-        Self {
-            location: vec![Point::default(); location_count],
-            distance_matrix: vec![vec![0.; location_count]; location_count],
-        }
-    }
-}
-
 #[derive(Clone)]
 struct SolveData {
     select_size: usize,
@@ -62,7 +62,7 @@ struct SolveData {
 impl SolveData {
     fn new(
         location_count: usize,
-        point_data: &PointData,
+        distance_matrix: &[Vec<f32>],
         neighbour_distance: f32,
         select_size: usize,
     ) -> Self {
@@ -79,7 +79,7 @@ impl SolveData {
             data.remaining_points.insert(i);
         }
         for (index, row) in data.adjacency_matrix.iter_mut().enumerate() {
-            for (point, distance) in point_data.distance_matrix[index].iter().enumerate() {
+            for (point, distance) in distance_matrix[index].iter().enumerate() {
                 if *distance <= neighbour_distance {
                     row.push(point);
                 }
@@ -90,6 +90,18 @@ impl SolveData {
     }
 }
 
+fn min_pairwise_distance(indices: &[usize], distance_matrix: &[Vec<f32>]) -> f32 {
+    let mut min_distance = f32::INFINITY;
+
+    for (position, &point_a) in indices.iter().enumerate() {
+        for &point_b in &indices[position + 1..] {
+            min_distance = min_distance.min(distance_matrix[point_a][point_b]);
+        }
+    }
+
+    min_distance
+}
+
 fn search(mut solve_data: Box<SolveData>) -> Option<Box<SolveData>> {
     if solve_data.selected_points.len() >= solve_data.select_size {
         return Some(solve_data);
@@ -128,83 +140,263 @@ pub fn solve_p_dispersion(
     input_array: &[Point],
     placements: u32,
 ) -> Result<Box<[usize]>, NoPossibleDispersion> {
-    let input_size = input_array.len();
+    solve_p_dispersion_with_metric(input_array, placements, &Euclidean)
+}
 
-    let mut point_data = PointData::new(input_size);
-    let mut possible_point_distance = Vec::new();
+/// Same as [`solve_p_dispersion`], but lets the caller pick how distance
+/// between two points is measured instead of assuming straight-line
+/// Euclidean distance. A metric may report `f32::INFINITY` for a pair that
+/// cannot reach each other at all (e.g. blocked by an obstacle); such a pair
+/// is simply never treated as conflicting.
+pub fn solve_p_dispersion_with_metric(
+    input_array: &[Point],
+    placements: u32,
+    metric: &dyn DistanceMetric,
+) -> Result<Box<[usize]>, NoPossibleDispersion> {
+    let distance_matrix = build_distance_matrix(input_array, metric);
 
-    for (point_input, point_data) in zip(input_array, point_data.location.iter_mut()) {
-        point_data.set(point_input);
-    }
+    let greedy_selection = greedy_dispersion(&distance_matrix, placements as usize);
+    // `greedy_dispersion` clamps to the input size, so only a full-sized
+    // selection is a feasible warm start.
+    let full_sized = greedy_selection.len() == placements as usize;
+    let warm_start = (full_sized && greedy_selection.len() >= 2).then(|| {
+        let objective = min_pairwise_distance(&greedy_selection, &distance_matrix);
+        (greedy_selection, objective)
+    });
 
-    for (point_a, row) in point_data.distance_matrix.iter_mut().enumerate() {
-        for (point_b, distance) in row.iter_mut().enumerate() {
-            *distance = point_data.location[point_a].get_distance(&point_data.location[point_b]);
-            possible_point_distance.push(*distance);
-        }
-    }
+    solve_from_distance_matrix(distance_matrix, placements, warm_start)
+}
+
+/// Same as [`solve_p_dispersion`], but takes an already-computed distance
+/// matrix directly rather than deriving one from [`Point`] coordinates. This
+/// is how a caller feeds in geodesic/shortest-path distances around
+/// obstacles that no simple metric over coordinates could express.
+pub fn solve_p_dispersion_from_matrix(
+    distance_matrix: Vec<Vec<f32>>,
+    placements: u32,
+) -> Result<Box<[usize]>, NoPossibleDispersion> {
+    solve_from_distance_matrix(distance_matrix, placements, None)
+}
+
+fn solve_from_distance_matrix(
+    distance_matrix: Vec<Vec<f32>>,
+    placements: u32,
+    warm_start: Option<(Box<[usize]>, f32)>,
+) -> Result<Box<[usize]>, NoPossibleDispersion> {
+    let input_size = distance_matrix.len();
 
+    let mut possible_point_distance: Vec<f32> = distance_matrix
+        .iter()
+        .enumerate()
+        .flat_map(|(point_a, row)| row[point_a + 1..].iter().copied())
+        .filter(|distance| distance.is_finite())
+        .collect();
     possible_point_distance.sort_by(f32::total_cmp);
+    possible_point_distance.dedup();
+    if possible_point_distance.is_empty() {
+        // No two points share a finite distance (e.g. a single point, or
+        // every pair blocked), so no threshold ever conflicts anything; any
+        // placeholder value keeps the bisection below well-defined.
+        possible_point_distance.push(0.);
+    }
 
+    // Any threshold below the greedy 2-approximation's achieved dispersion is
+    // trivially feasible, and the optimum can only be at least as good, so
+    // warm-start the bisection there instead of testing the lower half of
+    // thresholds the branch-and-bound would otherwise have to rule out. The
+    // greedy's own selection seeds `best_result` too, so a threshold that
+    // only ever matches the greedy's objective still has a feasible result
+    // on hand instead of relying on the bisection to re-discover it.
     let mut left_index = 0;
+    let mut largest_distance: Option<f32> = None;
+    let mut best_result: Option<HashSet<usize>> = None;
+
+    if let Some((selected, objective)) = warm_start {
+        left_index = possible_point_distance.partition_point(|&d| d < objective);
+        largest_distance = Some(objective);
+        best_result = Some(selected.into_iter().collect());
+    } else if let Some(result) = search(Box::new(SolveData::new(
+        input_size,
+        &distance_matrix,
+        f32::NEG_INFINITY,
+        placements as usize,
+    ))) {
+        // Without a warm start there's no feasible result to fall back on yet,
+        // and every real candidate threshold conflicts at least the pair that
+        // defines it, so the bisection alone can never witness the case where
+        // the optimum sits exactly at `possible_point_distance[0]` (e.g.
+        // `placements == input_size`, which must include that pair). A
+        // threshold below every real candidate has an empty conflict graph
+        // and is always feasible whenever `placements <= input_size`, so it
+        // establishes that baseline directly instead of leaving it
+        // undiscoverable.
+        best_result = Some(result.selected_points);
+    }
+
     let mut right_index = possible_point_distance.len();
-    let mut largest_distance: f32 = 0.;
-    let mut best_result: Option<Box<SolveData>> = None;
 
     while left_index < right_index {
         let target = (left_index + right_index) / 2;
 
         match search(Box::new(SolveData::new(
             input_size,
-            &point_data,
+            &distance_matrix,
             possible_point_distance[target],
             placements as usize,
         ))) {
             Some(result) => {
-                right_index = target - 1;
-                if possible_point_distance[target] > largest_distance {
-                    largest_distance = possible_point_distance[target];
-                    best_result = Some(result);
+                left_index = target + 1;
+                if largest_distance.is_none_or(|largest| possible_point_distance[target] >= largest)
+                {
+                    largest_distance = Some(possible_point_distance[target]);
+                    best_result = Some(result.selected_points);
                 }
             }
             None => {
-                left_index = target + 1;
+                right_index = target;
             }
         }
     }
 
     // [FIXME] Bandaid solution to the termination of the binary search
+    let left_index = left_index.min(possible_point_distance.len() - 1);
+    let right_index = right_index.min(possible_point_distance.len() - 1);
 
     if let Some(result) = search(Box::new(SolveData::new(
         input_size,
-        &point_data,
+        &distance_matrix,
         possible_point_distance[left_index],
         placements as usize,
-    ))) && possible_point_distance[left_index] > largest_distance
+    ))) && largest_distance.is_none_or(|largest| possible_point_distance[left_index] >= largest)
     {
-        largest_distance = possible_point_distance[left_index];
-        best_result = Some(result);
+        largest_distance = Some(possible_point_distance[left_index]);
+        best_result = Some(result.selected_points);
     }
 
     if let Some(result) = search(Box::new(SolveData::new(
         input_size,
-        &point_data,
+        &distance_matrix,
         possible_point_distance[right_index],
         placements as usize,
-    ))) && possible_point_distance[right_index] > largest_distance
+    ))) && largest_distance.is_none_or(|largest| possible_point_distance[right_index] >= largest)
     {
-        best_result = Some(result);
+        best_result = Some(result.selected_points);
     }
 
     match best_result {
-        Some(data) => {
-            // so it is possible...
-            Ok(data
-                .selected_points
-                .iter()
-                .cloned()
-                .collect::<Box<[usize]>>())
-        }
+        Some(selected_points) => Ok(selected_points.into_iter().collect::<Box<[usize]>>()),
         None => Err(NoPossibleDispersion),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal xorshift PRNG so these tests stay deterministic without
+    /// pulling in an external `rand` dependency, same rationale as
+    /// `annealing::Xorshift64`.
+    struct Xorshift32 {
+        state: u32,
+    }
+
+    impl Xorshift32 {
+        fn new(seed: u32) -> Self {
+            Self {
+                state: if seed == 0 { 0x9e3779b9 } else { seed },
+            }
+        }
+
+        fn next_u32(&mut self) -> u32 {
+            let mut x = self.state;
+            x ^= x << 13;
+            x ^= x >> 17;
+            x ^= x << 5;
+            self.state = x;
+            x
+        }
+
+        fn next_unit(&mut self) -> f32 {
+            (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+        }
+    }
+
+    fn random_points(rng: &mut Xorshift32, n: usize) -> Vec<Point> {
+        (0..n)
+            .map(|_| Point::new(rng.next_unit() * 10., rng.next_unit() * 10.))
+            .collect()
+    }
+
+    /// Exhaustively tries every `select_size`-sized subset of `points` and
+    /// returns the best achievable minimum pairwise distance, the ground
+    /// truth `solve_p_dispersion`'s warm-started bisection is supposed to
+    /// match exactly.
+    fn brute_force_dispersion(points: &[Point], select_size: usize) -> f32 {
+        let n = points.len();
+        let distance_matrix = build_distance_matrix(points, &Euclidean);
+        let mut best = f32::NEG_INFINITY;
+
+        for mask in 0u32..(1 << n) {
+            if mask.count_ones() as usize != select_size {
+                continue;
+            }
+            let indices: Vec<usize> = (0..n).filter(|i| mask & (1 << i) != 0).collect();
+            best = best.max(min_pairwise_distance(&indices, &distance_matrix));
+        }
+
+        best
+    }
+
+    #[test]
+    fn solve_p_dispersion_matches_brute_force() {
+        let mut rng = Xorshift32::new(0x1337);
+
+        for trial in 0..20 {
+            let n = 2 + (trial % 6);
+            let points = random_points(&mut rng, n);
+
+            for placements in 2..=n {
+                let expected = brute_force_dispersion(&points, placements);
+                let selected =
+                    solve_p_dispersion(&points, placements as u32).expect("always feasible here");
+                let distance_matrix = build_distance_matrix(&points, &Euclidean);
+                let achieved = min_pairwise_distance(&selected, &distance_matrix);
+
+                assert_eq!(selected.len(), placements);
+                assert!(
+                    (achieved - expected).abs() < 1e-3,
+                    "trial {trial}, n {n}, placements {placements}: got {achieved}, expected {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn greedy_warm_start_matches_the_no_warm_start_baseline() {
+        let mut rng = Xorshift32::new(0xf00d);
+
+        for trial in 0..20 {
+            let n = 2 + (trial % 6);
+            let points = random_points(&mut rng, n);
+            let distance_matrix = build_distance_matrix(&points, &Euclidean);
+
+            for placements in 2..=n as u32 {
+                let warm_started =
+                    solve_p_dispersion(&points, placements).expect("always feasible here");
+                let no_warm_start =
+                    solve_p_dispersion_from_matrix(distance_matrix.clone(), placements)
+                        .expect("always feasible here");
+
+                let warm_started_dispersion =
+                    min_pairwise_distance(&warm_started, &distance_matrix);
+                let no_warm_start_dispersion =
+                    min_pairwise_distance(&no_warm_start, &distance_matrix);
+
+                assert!(
+                    (warm_started_dispersion - no_warm_start_dispersion).abs() < 1e-3,
+                    "trial {trial}, n {n}, placements {placements}: warm-started {warm_started_dispersion}, baseline {no_warm_start_dispersion}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::Point;
+
+/// A pairwise distance function between two [`Point`]s, decoupling the
+/// solvers from hardcoded Euclidean distance. Implemented for any
+/// `Fn(&Point, &Point) -> f32` as well, so a plain closure works too.
+///
+/// Marking a pair `f32::INFINITY` means the points are unreachable from one
+/// another (e.g. separated by an obstacle) rather than simply far apart: the
+/// solvers treat an infinite-distance pair as never conflicting and never
+/// test it as a dispersion threshold.
+pub trait DistanceMetric {
+    fn distance(&self, a: &Point, b: &Point) -> f32;
+}
+
+impl<F> DistanceMetric for F
+where
+    F: Fn(&Point, &Point) -> f32,
+{
+    fn distance(&self, a: &Point, b: &Point) -> f32 {
+        self(a, b)
+    }
+}
+
+/// Straight-line distance. The default used by every solver when no metric
+/// is specified.
+pub struct Euclidean;
+
+impl DistanceMetric for Euclidean {
+    fn distance(&self, a: &Point, b: &Point) -> f32 {
+        ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt()
+    }
+}
+
+/// Taxicab distance.
+pub struct Manhattan;
+
+impl DistanceMetric for Manhattan {
+    fn distance(&self, a: &Point, b: &Point) -> f32 {
+        (a.x - b.x).abs() + (a.y - b.y).abs()
+    }
+}
+
+/// Euclidean distance without the final square root, for callers that only
+/// compare distances (dispersion thresholds are monotone under this, but the
+/// values are no longer true distances).
+pub struct SquaredEuclidean;
+
+impl DistanceMetric for SquaredEuclidean {
+    fn distance(&self, a: &Point, b: &Point) -> f32 {
+        (a.x - b.x).powi(2) + (a.y - b.y).powi(2)
+    }
+}
+
+/// Builds a dense distance matrix over `points` using `metric`, the shared
+/// first step of every solver entry point that starts from raw points rather
+/// than a precomputed matrix.
+pub fn build_distance_matrix(points: &[Point], metric: &dyn DistanceMetric) -> Vec<Vec<f32>> {
+    let n = points.len();
+    let mut matrix = vec![vec![0.; n]; n];
+
+    for point_a in 0..n {
+        for point_b in 0..n {
+            matrix[point_a][point_b] = metric.distance(&points[point_a], &points[point_b]);
+        }
+    }
+
+    matrix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solve_p_dispersion_with_metric;
+
+    #[test]
+    fn euclidean_matches_straight_line_distance() {
+        let a = Point::new(0., 0.);
+        let b = Point::new(3., 4.);
+        assert_eq!(Euclidean.distance(&a, &b), 5.);
+    }
+
+    #[test]
+    fn manhattan_matches_taxicab_distance() {
+        let a = Point::new(0., 0.);
+        let b = Point::new(3., 4.);
+        assert_eq!(Manhattan.distance(&a, &b), 7.);
+    }
+
+    #[test]
+    fn squared_euclidean_skips_the_final_sqrt() {
+        let a = Point::new(0., 0.);
+        let b = Point::new(3., 4.);
+        assert_eq!(SquaredEuclidean.distance(&a, &b), 25.);
+    }
+
+    #[test]
+    fn build_distance_matrix_is_symmetric_with_a_zero_diagonal() {
+        let points = [Point::new(0., 0.), Point::new(3., 4.), Point::new(-1., 2.)];
+        let matrix = build_distance_matrix(&points, &Euclidean);
+
+        for i in 0..points.len() {
+            assert_eq!(matrix[i][i], 0.);
+            for j in 0..points.len() {
+                assert_eq!(matrix[i][j], matrix[j][i]);
+            }
+        }
+    }
+
+    /// Blocks every pair of points sharing an x-coordinate, standing in for
+    /// an obstacle metric: same contract (`f32::INFINITY` for an unreachable
+    /// pair) as a geodesic/shortest-path metric around a real obstacle,
+    /// without needing one here.
+    struct BlockSameColumn;
+
+    impl DistanceMetric for BlockSameColumn {
+        fn distance(&self, a: &Point, b: &Point) -> f32 {
+            if a.x == b.x && a.y != b.y {
+                f32::INFINITY
+            } else {
+                Euclidean.distance(a, b)
+            }
+        }
+    }
+
+    fn min_pairwise(points: &[Point], indices: &[usize], metric: &dyn DistanceMetric) -> f32 {
+        let mut min_distance = f32::INFINITY;
+        for (position, &a) in indices.iter().enumerate() {
+            for &b in &indices[position + 1..] {
+                min_distance = min_distance.min(metric.distance(&points[a], &points[b]));
+            }
+        }
+        min_distance
+    }
+
+    /// Exhaustively tries every `select_size`-sized subset of `points` and
+    /// returns the best achievable minimum pairwise distance under `metric`,
+    /// the ground truth the bisection + greedy warm start are supposed to
+    /// match even when some pairs are unreachable.
+    fn brute_force_dispersion(points: &[Point], select_size: usize, metric: &dyn DistanceMetric) -> f32 {
+        let n = points.len();
+        let mut best = f32::NEG_INFINITY;
+
+        for mask in 0u32..(1 << n) {
+            if mask.count_ones() as usize != select_size {
+                continue;
+            }
+            let indices: Vec<usize> = (0..n).filter(|i| mask & (1 << i) != 0).collect();
+            best = best.max(min_pairwise(points, &indices, metric));
+        }
+
+        best
+    }
+
+    fn approx_eq(a: f32, b: f32) -> bool {
+        if a.is_infinite() && b.is_infinite() {
+            a.is_sign_positive() == b.is_sign_positive()
+        } else {
+            (a - b).abs() < 1e-3
+        }
+    }
+
+    #[test]
+    fn infinite_pairs_flow_through_the_bisection_and_warm_start() {
+        // Two points per column, three columns: every same-column pair is
+        // unreachable, exercising both `build_distance_matrix`'s non-finite
+        // entries and the skip-non-finite filtering in
+        // `solve_from_distance_matrix`'s candidate thresholds and greedy
+        // warm start.
+        let points = [
+            Point::new(0., 0.),
+            Point::new(0., 1.),
+            Point::new(5., 0.),
+            Point::new(5., 1.),
+            Point::new(10., 0.),
+            Point::new(10., 1.),
+        ];
+
+        for placements in 1..=points.len() {
+            let expected = brute_force_dispersion(&points, placements, &BlockSameColumn);
+            let indices =
+                solve_p_dispersion_with_metric(&points, placements as u32, &BlockSameColumn)
+                    .expect("every size up to the full input is feasible here");
+
+            assert_eq!(indices.len(), placements);
+
+            if placements >= 2 {
+                let achieved = min_pairwise(&points, &indices, &BlockSameColumn);
+                assert!(
+                    approx_eq(achieved, expected),
+                    "placements {placements}: got {achieved}, expected {expected}"
+                );
+            }
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use std::{cell::RefCell, iter::zip, rc::Rc};
 
-use crate::Point;
+use crate::{
+    Point,
+    bitset::{BitMatrix, BitSet},
+};
 
 #[derive(Debug)]
 struct PointData {
@@ -18,158 +21,32 @@ impl PointData {
     }
 }
 
+/// The conflict graph, backed by [`BitMatrix`] with `u64` lanes.
 struct AdjacencyMatrix {
-    data: Box<[PointVec]>,
+    rows: BitMatrix<u64>,
 }
 
 impl AdjacencyMatrix {
-    fn new(location_count: u8, point_data: &PointData, neighbour_distance: f32) -> Self {
-        let mut data = vec![PointVec::new(location_count, false); location_count as usize];
+    fn new(location_count: usize, point_data: &PointData, neighbour_distance: f32) -> Self {
+        let mut rows = BitMatrix::new(location_count, location_count);
 
-        for (index, row) in data.iter_mut().enumerate() {
-            for (point, distance) in point_data.distance_matrix[index].iter().enumerate() {
+        for (index, row) in point_data.distance_matrix.iter().enumerate() {
+            for (point, distance) in row.iter().enumerate() {
                 if *distance <= neighbour_distance {
-                    row.insert(point);
+                    rows.insert(index, point);
                 }
             }
         }
 
-        Self {
-            data: data.into_boxed_slice(),
-        }
-    }
-}
-
-#[derive(Clone)]
-struct PointVec {
-    data: [u64; 3],
-    true_count: u8,
-}
-
-impl PointVec {
-    fn new(location_count: u8, fill: bool) -> Self {
-        assert!(location_count < 64 * 3);
-
-        if fill {
-            let mut ret = Self {
-                data: [u64::MAX; 3],
-                true_count: location_count,
-            };
-
-            for sector in (location_count as usize / 64 + 1)..3 {
-                ret.data[sector] = 0;
-            }
-            ret.data[location_count as usize / 64] &= 2_u64.pow(location_count as u32 % 64) - 1;
-
-            ret
-        } else {
-            Self {
-                data: [0; 3],
-                true_count: 0,
-            }
-        }
-    }
-
-    fn reset(&mut self, location_count: u8, fill: bool) {
-        if fill {
-            for sector in self.data.iter_mut() {
-                *sector = u64::MAX;
-            }
-
-            for sector in (location_count as usize / 64 + 1)..3 {
-                self.data[sector] = 0;
-            }
-            self.data[location_count as usize / 64] &= 2_u64.pow(location_count as u32 % 64) - 1;
-
-            self.true_count = location_count;
-        } else {
-            for sector in self.data.iter_mut() {
-                *sector = 0;
-            }
-            self.true_count = 0;
-        }
-    }
-
-    fn len(&self) -> u8 {
-        self.true_count
+        Self { rows }
     }
 
-    fn copy(&mut self, copy_src: &PointVec) {
-        for sector in 0..self.data.len() {
-            self.data[sector] = copy_src.data[sector];
-        }
-        self.true_count = copy_src.true_count;
-    }
-
-    fn next(&self) -> Option<usize> {
-        self.data
-            .iter()
-            .position(|value| *value != 0)
-            .map(|index| 64 * index + self.data[index].trailing_zeros() as usize)
-    }
-
-    fn subtract_and_copy(&mut self, lhs: &PointVec, rhs: &PointVec) {
-        self.true_count = 0;
-
-        for index in 0..self.data.len() {
-            self.data[index] = lhs.data[index] & !rhs.data[index];
-            self.true_count += self.data[index].count_ones() as u8;
-        }
-    }
-
-    fn remove(&mut self, index: usize) {
-        let sector = index / 64;
-        let bitmask = 1 << (index % 64);
-
-        self.true_count -= (self.data[sector] & bitmask == bitmask) as u8;
-        self.data[sector] &= !bitmask;
-    }
-
-    fn insert_and_copy(&mut self, lhs: &PointVec, index: usize) {
-        let target_sector = index / 64;
-        let bitmask = 1 << (index % 64);
-
-        for sector in 0..self.data.len() {
-            if sector == target_sector {
-                self.true_count = lhs.true_count + (lhs.data[sector] & bitmask != bitmask) as u8;
-                self.data[sector] = lhs.data[sector] | bitmask;
-                continue;
-            }
-
-            self.data[sector] = lhs.data[sector]
-        }
-    }
-
-    fn insert(&mut self, index: usize) {
-        let sector = index / 64;
-        let bitmask = 1 << (index % 64);
-
-        self.true_count += (self.data[sector] & bitmask != bitmask) as u8;
-        self.data[sector] |= bitmask;
+    fn row(&self, index: usize) -> &[u64] {
+        self.rows.row(index)
     }
 }
 
-impl From<PointVec> for Box<[usize]> {
-    fn from(val: PointVec) -> Self {
-        let mut result: Vec<usize> = Vec::new();
-        for (index, value) in val
-            .data
-            .iter()
-            .enumerate()
-            .filter(|(_index, value)| **value != 0)
-        {
-            // copy out the u64 ref
-            let mut value = *value;
-
-            while value > 0 {
-                result.push(index * 64 + value.trailing_zeros() as usize);
-                value ^= 1 << value.trailing_zeros();
-            }
-        }
-
-        result.into_boxed_slice()
-    }
-}
+type PointVec = BitSet<u64>;
 
 #[derive(Clone)]
 struct SolveData {
@@ -178,14 +55,14 @@ struct SolveData {
 }
 
 impl SolveData {
-    fn new(location_count: u8) -> Self {
+    fn new(location_count: usize) -> Self {
         Self {
             selected_points: PointVec::new(location_count, false),
             remaining_points: PointVec::new(location_count, true),
         }
     }
 
-    fn reset(&mut self, location_count: u8) {
+    fn reset(&mut self, location_count: usize) {
         self.selected_points.reset(location_count, false);
         self.remaining_points.reset(location_count, true);
     }
@@ -193,14 +70,14 @@ impl SolveData {
 
 struct SolveStack {
     data: Box<[Rc<RefCell<SolveData>>]>,
-    idx: u8,
+    idx: usize,
 }
 
 impl SolveStack {
-    fn new(max_search_depth: u8, location_count: u8) -> Self {
+    fn new(max_search_depth: usize, location_count: usize) -> Self {
         Self {
             data: {
-                let mut v = Vec::with_capacity(max_search_depth as usize);
+                let mut v = Vec::with_capacity(max_search_depth);
                 (0..max_search_depth)
                     .for_each(|_| v.push(Rc::new(RefCell::new(SolveData::new(location_count)))));
                 v.into_boxed_slice()
@@ -210,9 +87,9 @@ impl SolveStack {
     }
 
     fn alloc(&mut self) -> Rc<RefCell<SolveData>> {
-        assert!((self.idx as usize) < self.data.len());
+        assert!(self.idx < self.data.len());
 
-        let ret = self.data[self.idx as usize].clone();
+        let ret = self.data[self.idx].clone();
         self.idx += 1;
 
         ret
@@ -222,28 +99,17 @@ impl SolveStack {
         self.idx -= 1;
     }
 
-    fn reset(&mut self, location_count: u8) {
+    fn reset(&mut self, location_count: usize) {
         self.idx = 0;
         self.data[0].borrow_mut().reset(location_count);
     }
 }
 
-impl Point {
-    fn set(&mut self, point: &Point) {
-        self.x = point.x;
-        self.y = point.y;
-    }
-
-    fn get_distance(&self, point: &Point) -> f32 {
-        ((self.x - point.x).powi(2) + (self.y - point.y).powi(2)).sqrt()
-    }
-}
-
 fn search(
     solve_data: Rc<RefCell<SolveData>>,
     stack: &mut SolveStack,
     adjacency_matrix: &AdjacencyMatrix,
-    select_size: u8,
+    select_size: usize,
 ) -> Option<Rc<RefCell<SolveData>>> {
     let mut mut_solve_data = solve_data.borrow_mut();
 
@@ -272,7 +138,7 @@ fn search(
         .insert_and_copy(&mut_solve_data.selected_points, point);
     mut_new_data.remaining_points.subtract_and_copy(
         &mut_solve_data.remaining_points,
-        &adjacency_matrix.data[point],
+        adjacency_matrix.row(point),
     );
 
     drop(mut_new_data);
@@ -288,12 +154,12 @@ fn search(
     search(solve_data, stack, adjacency_matrix, select_size)
 }
 
-pub fn naive_solver(input_data: &[Point], placements: u8) -> Option<Box<[usize]>> {
-    let input_size = input_data.len() as u8;
-    let mut point_data = PointData::new(input_data.len());
+pub fn naive_solver(input_data: &[Point], placements: u32) -> Option<Box<[usize]>> {
+    let input_size = input_data.len();
+    let mut point_data = PointData::new(input_size);
 
     for (point_input, point_data) in zip(input_data, point_data.location.iter_mut()) {
-        point_data.set(point_input);
+        *point_data = *point_input;
     }
 
     for (point_a, row) in point_data.distance_matrix.iter_mut().enumerate() {
@@ -302,17 +168,46 @@ pub fn naive_solver(input_data: &[Point], placements: u8) -> Option<Box<[usize]>
         }
     }
 
-    let mut possible_point_distance = point_data
+    let mut possible_point_distance: Vec<f32> = point_data
         .distance_matrix
-        .first()
-        .expect("Distance matrix must not be empty")
-        .clone();
+        .iter()
+        .flatten()
+        .copied()
+        .collect();
     possible_point_distance.sort_by(f32::total_cmp);
+    possible_point_distance.dedup();
+    if possible_point_distance.is_empty() {
+        // No points at all, so there is no real candidate to bisect over; any
+        // placeholder value keeps the search below well-defined.
+        possible_point_distance.push(0.);
+    }
 
     let mut left_index = 0;
-    let mut right_index = possible_point_distance.len() - 1;
+    let mut right_index = possible_point_distance.len();
     let mut best_result = PointVec::new(input_size, false);
-    let mut stack = SolveStack::new(input_size, input_size);
+    let mut found = false;
+    // Reaching the base case after `input_size` successful picks needs
+    // `input_size + 1` frames (the root plus one per pick), same as
+    // `MaxPlacementsSession`'s stack in core.rs.
+    let mut stack = SolveStack::new(input_size + 1, input_size);
+
+    // Every real candidate distance conflicts at least the pair that defines
+    // it, so the bisection below can never witness a selection whose
+    // achieved dispersion coincides exactly with the smallest real candidate
+    // (e.g. every finite distance in the matrix is identical). A threshold
+    // below every real candidate has an empty conflict graph and is always
+    // feasible whenever `placements <= input_size`, so probe it directly to
+    // seed that baseline instead of leaving it undiscoverable.
+    stack.reset(input_size);
+    if let Some(result) = search(
+        stack.alloc(),
+        &mut stack,
+        &AdjacencyMatrix::new(input_size, &point_data, f32::NEG_INFINITY),
+        placements as usize,
+    ) {
+        best_result.copy(&result.borrow().selected_points);
+        found = true;
+    }
 
     while left_index < right_index {
         stack.reset(input_size);
@@ -322,19 +217,144 @@ pub fn naive_solver(input_data: &[Point], placements: u8) -> Option<Box<[usize]>
             stack.alloc(),
             &mut stack,
             &AdjacencyMatrix::new(input_size, &point_data, possible_point_distance[target]),
-            placements,
+            placements as usize,
         ) {
             Some(result) => {
                 left_index = target + 1;
                 best_result.copy(&result.borrow().selected_points);
+                found = true;
             }
             None => right_index = target,
         }
     }
 
-    if best_result.true_count == 0 {
-        None
-    } else {
+    if found {
         Some(best_result.into())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal xorshift PRNG so these tests stay deterministic without
+    /// pulling in an external `rand` dependency, same rationale as
+    /// `annealing::Xorshift64`.
+    struct Xorshift32 {
+        state: u32,
+    }
+
+    impl Xorshift32 {
+        fn new(seed: u32) -> Self {
+            Self {
+                state: if seed == 0 { 0x9e3779b9 } else { seed },
+            }
+        }
+
+        fn next_u32(&mut self) -> u32 {
+            let mut x = self.state;
+            x ^= x << 13;
+            x ^= x >> 17;
+            x ^= x << 5;
+            self.state = x;
+            x
+        }
+
+        fn next_unit(&mut self) -> f32 {
+            (self.next_u32() >> 8) as f32 / (1u32 << 24) as f32
+        }
+    }
+
+    fn random_points(rng: &mut Xorshift32, n: usize) -> Vec<Point> {
+        (0..n)
+            .map(|_| Point::new(rng.next_unit() * 10., rng.next_unit() * 10.))
+            .collect()
+    }
+
+    fn min_pairwise(points: &[Point], indices: &[usize]) -> f32 {
+        let mut min_distance = f32::INFINITY;
+        for (position, &a) in indices.iter().enumerate() {
+            for &b in &indices[position + 1..] {
+                min_distance = min_distance.min(points[a].get_distance(&points[b]));
+            }
+        }
+        min_distance
+    }
+
+    /// Exhaustively tries every `select_size`-sized subset of `points` and
+    /// returns the best achievable minimum pairwise distance: the ground
+    /// truth `naive_solver`'s bisection is supposed to match exactly.
+    fn brute_force_dispersion(points: &[Point], select_size: usize) -> Option<f32> {
+        let n = points.len();
+        if select_size > n {
+            return None;
+        }
+        if select_size < 2 {
+            return Some(f32::INFINITY);
+        }
+
+        let mut best: Option<f32> = None;
+        for mask in 0u32..(1 << n) {
+            if mask.count_ones() as usize != select_size {
+                continue;
+            }
+            let indices: Vec<usize> = (0..n).filter(|i| mask & (1 << i) != 0).collect();
+            let dispersion = min_pairwise(points, &indices);
+            best = Some(best.map_or(dispersion, |b: f32| b.max(dispersion)));
+        }
+        best
+    }
+
+    #[test]
+    fn naive_solver_matches_brute_force() {
+        let mut rng = Xorshift32::new(0xbadf00d);
+
+        for trial in 0..20 {
+            let n = 2 + (trial % 6);
+            let points = random_points(&mut rng, n);
+
+            for placements in 1..=n {
+                let expected = brute_force_dispersion(&points, placements);
+                let got = naive_solver(&points, placements as u32);
+
+                match (got, expected) {
+                    (Some(indices), Some(expected_dispersion)) => {
+                        assert_eq!(indices.len(), placements);
+                        if placements >= 2 {
+                            let achieved = min_pairwise(&points, &indices);
+                            assert!(
+                                (achieved - expected_dispersion).abs() < 1e-3,
+                                "trial {trial}, n {n}, placements {placements}: got {achieved}, expected {expected_dispersion}"
+                            );
+                        }
+                    }
+                    (None, None) => {}
+                    (got, expected) => panic!(
+                        "trial {trial}, n {n}, placements {placements}: got {got:?}, expected {expected:?}"
+                    ),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn empty_input_does_not_panic() {
+        assert_eq!(naive_solver(&[], 0).map(|r| r.len()), Some(0));
+    }
+
+    #[test]
+    fn single_point_is_feasible() {
+        let points = [Point::new(0., 0.)];
+        assert_eq!(naive_solver(&points, 1).map(|r| r.len()), Some(1));
+    }
+
+    #[test]
+    fn identical_points_are_still_feasible() {
+        // Every finite distance in the matrix coincides with the smallest
+        // one, the edge case the binary search alone cannot witness.
+        let points = [Point::new(1., 1.), Point::new(1., 1.), Point::new(1., 1.)];
+        assert_eq!(naive_solver(&points, 2).map(|r| r.len()), Some(2));
     }
 }
@@ -0,0 +1,157 @@
+use std::{fmt::Write as _, fs, io, path::Path};
+
+use crate::Point;
+
+const PADDING: f32 = 1.0;
+const POINT_RADIUS: f32 = 0.15;
+const SELECTED_RADIUS: f32 = 0.25;
+
+/// Renders a dispersion solution as a standalone SVG document: every
+/// candidate point as a small gray circle, every selected point overdrawn
+/// with a highlighted circle, and (when `neighbour_radius` is positive) a
+/// dashed exclusion circle of that radius around each selected point so the
+/// achieved spacing is visible at a glance. The viewBox auto-fits the point
+/// bounding box with padding, so the result needs no further scaling.
+pub fn export_svg(points: &[Point], selected_indices: &[usize], neighbour_radius: f32) -> String {
+    if points.is_empty() {
+        return String::from("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 1 1\" />\n");
+    }
+
+    let (min_x, min_y, max_x, max_y) = bounding_box(points, neighbour_radius.max(0.));
+
+    let view_x = min_x - PADDING;
+    let view_y = min_y - PADDING;
+    let view_width = (max_x - min_x) + 2. * PADDING;
+    let view_height = (max_y - min_y) + 2. * PADDING;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{view_x} {view_y} {view_width} {view_height}\">"
+    )
+    .unwrap();
+
+    for point in points {
+        writeln!(
+            svg,
+            "  <circle cx=\"{}\" cy=\"{}\" r=\"{POINT_RADIUS}\" fill=\"gray\" />",
+            point.x, point.y
+        )
+        .unwrap();
+    }
+
+    for &index in selected_indices {
+        let point = &points[index];
+
+        if neighbour_radius > 0. {
+            writeln!(
+                svg,
+                "  <circle cx=\"{}\" cy=\"{}\" r=\"{neighbour_radius}\" fill=\"none\" stroke=\"#74c7ec\" stroke-dasharray=\"0.1,0.1\" />",
+                point.x, point.y
+            )
+            .unwrap();
+        }
+
+        writeln!(
+            svg,
+            "  <circle cx=\"{}\" cy=\"{}\" r=\"{SELECTED_RADIUS}\" fill=\"#74c7ec\" />",
+            point.x, point.y
+        )
+        .unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Convenience wrapper around [`export_svg`] that writes the document to
+/// `path` directly.
+pub fn export_svg_to_file(
+    path: impl AsRef<Path>,
+    points: &[Point],
+    selected_indices: &[usize],
+    neighbour_radius: f32,
+) -> io::Result<()> {
+    fs::write(path, export_svg(points, selected_indices, neighbour_radius))
+}
+
+fn bounding_box(points: &[Point], neighbour_radius: f32) -> (f32, f32, f32, f32) {
+    let mut min_x = f32::INFINITY;
+    let mut min_y = f32::INFINITY;
+    let mut max_x = f32::NEG_INFINITY;
+    let mut max_y = f32::NEG_INFINITY;
+
+    for point in points {
+        min_x = min_x.min(point.x - neighbour_radius);
+        min_y = min_y.min(point.y - neighbour_radius);
+        max_x = max_x.max(point.x + neighbour_radius);
+        max_y = max_y.max(point.y + neighbour_radius);
+    }
+
+    (min_x, min_y, max_x, max_y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounding_box_hugs_a_single_point() {
+        let points = [Point::new(3., 4.)];
+        assert_eq!(bounding_box(&points, 0.), (3., 4., 3., 4.));
+    }
+
+    #[test]
+    fn bounding_box_grows_by_the_neighbour_radius() {
+        let points = [Point::new(3., 4.)];
+        assert_eq!(bounding_box(&points, 0.5), (2.5, 3.5, 3.5, 4.5));
+    }
+
+    #[test]
+    fn bounding_box_spans_collinear_points() {
+        let points = [Point::new(0., 0.), Point::new(5., 0.), Point::new(2., 0.)];
+        assert_eq!(bounding_box(&points, 0.), (0., 0., 5., 0.));
+    }
+
+    #[test]
+    fn export_svg_of_no_points_is_a_fixed_placeholder() {
+        let svg = export_svg(&[], &[], 0.);
+        assert_eq!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 1 1\" />\n"
+        );
+    }
+
+    #[test]
+    fn export_svg_draws_one_circle_per_point_plus_one_per_selected_point() {
+        let points = [Point::new(0., 0.), Point::new(1., 0.), Point::new(0., 1.)];
+        let svg = export_svg(&points, &[0, 2], 0.);
+
+        assert_eq!(svg.matches("<circle").count(), points.len() + 2);
+        assert_eq!(svg.matches("fill=\"gray\"").count(), points.len());
+        assert_eq!(svg.matches("fill=\"#74c7ec\"").count(), 2);
+        assert!(!svg.contains("stroke-dasharray"));
+    }
+
+    #[test]
+    fn export_svg_adds_an_exclusion_circle_per_selected_point_when_radius_is_positive() {
+        let points = [Point::new(0., 0.), Point::new(1., 0.)];
+        let svg = export_svg(&points, &[0, 1], 0.4);
+
+        assert_eq!(svg.matches("stroke-dasharray").count(), 2);
+    }
+
+    #[test]
+    fn export_svg_view_box_fits_the_points_with_padding() {
+        let points = [Point::new(0., 0.), Point::new(4., 3.)];
+        let svg = export_svg(&points, &[], 0.);
+
+        assert!(svg.contains(&format!(
+            "viewBox=\"{} {} {} {}\"",
+            -PADDING,
+            -PADDING,
+            4. + 2. * PADDING,
+            3. + 2. * PADDING
+        )));
+    }
+}